@@ -0,0 +1,500 @@
+//! Provides centrality measures that rank the nodes of a graph by importance.
+//!
+//! Every measure assigns a score to each node of the graph, with higher scores denoting
+//! more central nodes. Measures based on shortest paths follow relations in their
+//! direction and weight them by a [`PathCost`].
+
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::NodeAddress;
+use crate::path_queries::best_first::{best_first_search, SearchVisitor};
+use crate::path_queries::dijkstra::DijkstraSearch;
+use crate::path_queries::PathCost;
+use crate::traversal::Control;
+use std::collections::{HashMap, HashSet};
+
+/// Determines the degree centrality of every node, i.e. the number of relations
+/// in the specified direction, normalized by the number of other nodes.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn degree_centrality<G>(
+    graph: &G,
+    direction: Direction,
+) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+where
+    G: Graph,
+{
+    let others = graph.node_count().saturating_sub(1).max(1) as f32;
+    graph
+        .nodes()
+        .map(|(address, _)| {
+            let degree = graph
+                .iter_local_neighbors_directed(&address, direction)?
+                .count();
+            Ok((address, degree as f32 / others))
+        })
+        .collect()
+}
+
+/// Ranks nodes by the PageRank algorithm, i.e. by the probability of a random walk
+/// along the relations of the graph ending up at each node.
+#[derive(Debug, Clone, Copy)]
+pub struct PageRank {
+    /// The probability of following a relation rather than jumping to a random node.
+    pub damping: f32,
+    /// The total change of all scores in one iteration below which the ranking has converged.
+    pub tolerance: f32,
+    /// The maximum number of iterations.
+    pub max_iterations: usize,
+}
+
+impl Default for PageRank {
+    fn default() -> Self {
+        PageRank {
+            damping: 0.85,
+            tolerance: 1e-6,
+            max_iterations: 100,
+        }
+    }
+}
+
+impl PageRank {
+    /// Determines the PageRank of every node. The scores sum up to one.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if the graph contains relations to nodes that do not exist.
+    pub fn rank<G>(&self, graph: &G) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+    where
+        G: Graph,
+    {
+        self.iterate(graph, &HashMap::new())
+    }
+
+    /// Determines the personalized PageRank of every node, where random jumps lead
+    /// to the nodes of `personalization` in proportion to their weights instead of
+    /// to any node. The scores sum up to one.
+    ///
+    /// Nodes missing from `personalization` are never jumped to. If the weights do not
+    /// sum up to a positive value, this is the same as [`PageRank::rank`].
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `personalization` contains nodes that are not part of the graph,
+    /// or if the graph contains relations to nodes that do not exist.
+    pub fn personalized<G>(
+        &self,
+        graph: &G,
+        personalization: &HashMap<NodeAddress, f32>,
+    ) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+    where
+        G: Graph,
+    {
+        self.iterate(graph, personalization)
+    }
+
+    fn iterate<G>(
+        &self,
+        graph: &G,
+        personalization: &HashMap<NodeAddress, f32>,
+    ) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+    where
+        G: Graph,
+    {
+        let nodes: Vec<_> = graph.nodes().map(|(address, _)| address).collect();
+        let index: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, address)| (address.clone(), index))
+            .collect();
+        let lookup = |address: &NodeAddress| {
+            index
+                .get(address)
+                .copied()
+                .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone()))
+        };
+
+        let mut teleport = vec![0.0; nodes.len()];
+        for (address, weight) in personalization {
+            teleport[lookup(address)?] = weight.max(0.0);
+        }
+        let total: f32 = teleport.iter().sum();
+        if total > 0.0 {
+            teleport.iter_mut().for_each(|weight| *weight /= total);
+        } else {
+            teleport.fill(1.0 / nodes.len() as f32);
+        }
+
+        let outgoing = nodes
+            .iter()
+            .map(|address| {
+                graph
+                    .iter_local_neighbors(address)?
+                    .map(|neighbor| lookup(neighbor.address))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
+
+        let mut rank = teleport.clone();
+        for _ in 0..self.max_iterations {
+            // Walks ending in nodes without relations continue with a random jump.
+            let dangling: f32 = outgoing
+                .iter()
+                .zip(&rank)
+                .filter(|(targets, _)| targets.is_empty())
+                .map(|(_, rank)| rank)
+                .sum();
+            let jump = self.damping * dangling + 1.0 - self.damping;
+            let mut next: Vec<_> = teleport.iter().map(|weight| jump * weight).collect();
+            for (targets, rank) in outgoing.iter().zip(&rank) {
+                let share = self.damping * rank / targets.len() as f32;
+                for &target in targets {
+                    next[target] += share;
+                }
+            }
+
+            let change: f32 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+            rank = next;
+            if change < self.tolerance {
+                break;
+            }
+        }
+
+        Ok(nodes.into_iter().zip(rank).collect())
+    }
+}
+
+/// Determines the betweenness centrality of every node using Brandes' algorithm.
+///
+/// The betweenness of a node is the number of shortest paths between pairs of other
+/// nodes that pass through it, where pairs connected by several shortest paths contribute
+/// the fraction of paths passing through the node. Every ordered pair of nodes is counted.
+///
+/// Path costs must not be negative. Relations of zero cost are supported, but a cycle of zero
+/// total cost admits infinitely many shortest paths, for which the result is meaningless.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn betweenness_centrality<G, P>(
+    graph: &G,
+    path_cost: &P,
+) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+where
+    G: Graph,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let mut centrality: HashMap<_, _> = graph.nodes().map(|(address, _)| (address, 0.0)).collect();
+
+    for (source, _) in graph.nodes() {
+        let paths = ShortestPaths::search(graph, source.clone(), path_cost)?;
+
+        // Accumulate the dependencies of the source on each node, successors first.
+        let mut dependency: HashMap<&NodeAddress, f32> = HashMap::new();
+        for node in paths.order.iter().rev() {
+            let node_dependency = dependency.get(node).copied().unwrap_or_default();
+            let coefficient = (1.0 + node_dependency) / paths.count[node];
+            for predecessor in &paths.predecessors[node] {
+                *dependency.entry(predecessor).or_default() +=
+                    paths.count[predecessor] * coefficient;
+            }
+            if *node != source {
+                *centrality
+                    .get_mut(node)
+                    .ok_or_else(|| NodeAddressError::NodeNotFound(node.clone()))? +=
+                    node_dependency;
+            }
+        }
+    }
+
+    Ok(centrality)
+}
+
+/// Determines the closeness centrality of every node, i.e. the inverse of the average
+/// cost of the shortest paths from the node to all nodes reachable from it.
+///
+/// To remain comparable in graphs that are not strongly connected, the score is scaled by
+/// the fraction of other nodes that are reachable. Nodes that reach no other node score zero.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn closeness_centrality<G, P>(
+    graph: &G,
+    path_cost: &P,
+) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+where
+    G: Graph,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let others = graph.node_count().saturating_sub(1).max(1) as f32;
+    graph
+        .nodes()
+        .map(|(address, _)| {
+            let distances = DijkstraSearch.distances(graph, address.clone(), path_cost)?;
+            let reachable = (distances.len() - 1) as f32;
+            let total: f32 = distances.values().sum();
+            let closeness = if total > 0.0 {
+                reachable / total * reachable / others
+            } else {
+                0.0
+            };
+            Ok((address, closeness))
+        })
+        .collect()
+}
+
+/// All shortest paths from a single source, as required by Brandes' algorithm.
+struct ShortestPaths {
+    /// The reachable nodes, each following all of its predecessors.
+    order: Vec<NodeAddress>,
+    /// The number of shortest paths from the source to each node.
+    count: HashMap<NodeAddress, f32>,
+    /// The predecessors of each node on any of its shortest paths.
+    predecessors: HashMap<NodeAddress, Vec<NodeAddress>>,
+}
+
+impl ShortestPaths {
+    fn search<G, P>(graph: &G, source: NodeAddress, path_cost: &P) -> Result<Self, NodeAddressError>
+    where
+        G: Graph,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        let mut paths = ShortestPaths {
+            order: Vec::new(),
+            count: HashMap::new(),
+            predecessors: HashMap::new(),
+        };
+        best_first_search(graph, [source], path_cost, |_, _| 0.0, &mut paths)?;
+        paths.count_paths();
+        Ok(paths)
+    }
+
+    /// Orders the nodes after their predecessors and counts the shortest paths to each node.
+    ///
+    /// Nodes are expanded in order of their distance, but a relation of zero cost may lead to
+    /// a node at the same distance that was expanded first, so the expansion order may place
+    /// a node before one of its predecessors.
+    fn count_paths(&mut self) {
+        let mut order = Vec::with_capacity(self.order.len());
+        let mut visited = HashSet::new();
+        for node in &self.order {
+            if !visited.insert(node) {
+                continue;
+            }
+
+            // Emit each node once all of its predecessors were emitted.
+            let mut stack = vec![(node, 0)];
+            while let Some((current, next)) = stack.pop() {
+                match self.predecessors[current].get(next) {
+                    Some(predecessor) => {
+                        stack.push((current, next + 1));
+                        if visited.insert(predecessor) {
+                            stack.push((predecessor, 0));
+                        }
+                    }
+                    None => order.push(current.clone()),
+                }
+            }
+        }
+
+        for node in &order {
+            let predecessors = &self.predecessors[node];
+            let count = if predecessors.is_empty() {
+                1.0
+            } else {
+                predecessors
+                    .iter()
+                    .map(|predecessor| self.count.get(predecessor).copied().unwrap_or_default())
+                    .sum()
+            };
+            self.count.insert(node.clone(), count);
+        }
+        self.order = order;
+    }
+}
+
+impl<R> SearchVisitor<R> for ShortestPaths {
+    fn start(&mut self, node: &NodeAddress) {
+        self.predecessors.insert(node.clone(), Vec::new());
+    }
+
+    fn expand(&mut self, node: &NodeAddress, _g_score: f32) -> Control {
+        self.order.push(node.clone());
+        Control::Continue
+    }
+
+    fn improve(
+        &mut self,
+        from: &NodeAddress,
+        _edge: usize,
+        _relation: &R,
+        to: &NodeAddress,
+        _g_score: f32,
+    ) {
+        self.predecessors.insert(to.clone(), vec![from.clone()]);
+    }
+
+    fn tie(&mut self, from: &NodeAddress, _relation: &R, to: &NodeAddress) {
+        // A loop of zero cost does not lead to any new path.
+        if from != to {
+            self.predecessors
+                .get_mut(to)
+                .expect("node was reached")
+                .push(from.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::community::UniformWeight;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, LondonGraphStationsCost};
+    #[cfg(feature = "examples-movies")]
+    use crate::examples::movie_graph::{movie_graph, NodeType};
+    use crate::test_support::RelationValue;
+
+    #[test]
+    fn it_works() {
+        // A diamond a -> {b, c} -> d, followed by d -> e.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, ()).unwrap();
+        graph.link_to(&a, &c, ()).unwrap();
+        graph.link_to(&b, &d, ()).unwrap();
+        graph.link_to(&c, &d, ()).unwrap();
+        graph.link_to(&d, &e, ()).unwrap();
+
+        let betweenness = betweenness_centrality(&graph, &UniformWeight).unwrap();
+        assert_eq!(betweenness[&a], 0.0);
+        assert_eq!(betweenness[&b], 1.0);
+        assert_eq!(betweenness[&c], 1.0);
+        assert_eq!(betweenness[&d], 3.0);
+        assert_eq!(betweenness[&e], 0.0);
+
+        let closeness = closeness_centrality(&graph, &UniformWeight).unwrap();
+        assert_eq!(closeness[&a], 4.0 / 7.0);
+        assert_eq!(closeness[&d], 1.0 / 4.0);
+        assert_eq!(closeness[&e], 0.0);
+
+        let in_degree = degree_centrality(&graph, Direction::Incoming).unwrap();
+        let out_degree = degree_centrality(&graph, Direction::Outgoing).unwrap();
+        assert_eq!(in_degree[&d], 0.5);
+        assert_eq!(out_degree[&a], 0.5);
+        assert_eq!(out_degree[&e], 0.0);
+    }
+
+    #[test]
+    fn zero_cost_ties() {
+        // a -> c is expanded before a -> b -> c, which is just as short.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add(name));
+        graph.link_to(&a, &c, 1.0).unwrap();
+        graph.link_to(&a, &b, 1.0).unwrap();
+        graph.link_to(&b, &c, 0.0).unwrap();
+        graph.link_to(&c, &d, 1.0).unwrap();
+
+        let betweenness = betweenness_centrality(&graph, &RelationValue).unwrap();
+        assert_eq!(betweenness[&a], 0.0);
+        assert_eq!(betweenness[&b], 1.0);
+        assert_eq!(betweenness[&c], 2.0);
+        assert_eq!(betweenness[&d], 0.0);
+    }
+
+    #[test]
+    fn relations_need_not_be_cloned() {
+        struct Link;
+
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b] = ["a", "b"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, Link).unwrap();
+
+        let closeness = closeness_centrality(&graph, &UniformWeight).unwrap();
+        assert_eq!(closeness[&a], 1.0);
+        let betweenness = betweenness_centrality(&graph, &UniformWeight).unwrap();
+        assert_eq!(betweenness[&a], 0.0);
+    }
+
+    #[test]
+    fn page_rank() {
+        // A cycle a -> b -> c -> a ranks all nodes equally.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c] = ["a", "b", "c"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, ()).unwrap();
+        graph.link_to(&b, &c, ()).unwrap();
+        graph.link_to(&c, &a, ()).unwrap();
+
+        let rank = PageRank::default().rank(&graph).unwrap();
+        for address in [&a, &b, &c] {
+            assert!((rank[address] - 1.0 / 3.0).abs() < 1e-5);
+        }
+
+        // Jumping to `a` only favors `a` and its successor over `c`.
+        let personalized = PageRank::default()
+            .personalized(&graph, &HashMap::from([(a.clone(), 1.0)]))
+            .unwrap();
+        assert!(personalized[&a] > personalized[&b]);
+        assert!(personalized[&b] > personalized[&c]);
+        assert!((personalized.values().sum::<f32>() - 1.0).abs() < 1e-5);
+
+        let unknown = HashMap::from([(NodeAddress::from_local(graph.id(), 10), 1.0)]);
+        assert!(matches!(
+            PageRank::default().personalized(&graph, &unknown),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-movies")]
+    fn movie_people() {
+        // Relations lead from people to movies, so only people have outgoing relations.
+        let graph = movie_graph();
+        let out_degree = degree_centrality(&graph, Direction::Outgoing).unwrap();
+        let (most_connected, _) = out_degree.iter().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+        assert!(matches!(
+            graph.node(most_connected),
+            Ok(NodeType::Person(_))
+        ));
+        for (address, node) in graph.nodes() {
+            if let NodeType::Movie(_) = node {
+                assert_eq!(out_degree[&address], 0.0);
+            }
+        }
+
+        let rank = PageRank::default().rank(&graph).unwrap();
+        assert!((rank.values().sum::<f32>() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn london_hubs() {
+        let graph = london_graph();
+        let betweenness = betweenness_centrality(&graph, &LondonGraphStationsCost).unwrap();
+        assert_eq!(betweenness.len(), graph.node_count());
+
+        // The unconnected stations 5 and 200 are never passed through.
+        for island in [
+            NodeAddress::from_local(graph.id(), 4),
+            NodeAddress::from_local(graph.id(), 199),
+        ] {
+            assert_eq!(betweenness[&island], 0.0);
+        }
+        let (hub, _) = betweenness
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+
+        // The busiest hub is also well connected and close to the other stations.
+        let degree = degree_centrality(&graph, Direction::Outgoing).unwrap();
+        let closeness = closeness_centrality(&graph, &LondonGraphStationsCost).unwrap();
+        let mean_degree = degree.values().sum::<f32>() / degree.len() as f32;
+        let mean_closeness = closeness.values().sum::<f32>() / closeness.len() as f32;
+        assert!(degree[hub] > mean_degree);
+        assert!(closeness[hub] > mean_closeness);
+    }
+}
@@ -0,0 +1,458 @@
+//! Provides community detection.
+//!
+//! Relations are treated as undirected edges whose weight is given by a [`PathCost`];
+//! here, the value denotes the strength of a relation rather than a distance. Use
+//! [`UniformWeight`] to weight all relations equally. Relations with a weight that is
+//! not positive and finite are ignored.
+//!
+//! Both algorithms visit nodes in the order the graph yields them and are deterministic.
+
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::path_queries::PathCost;
+use std::collections::{BTreeMap, HashMap};
+
+/// The smallest modularity gain considered an improvement; guards against rounding errors.
+const MIN_GAIN: f64 = 1e-12;
+
+/// The maximum number of passes label propagation makes over all nodes.
+const MAX_PASSES: usize = 100;
+
+/// Weights every relation with `1.0`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UniformWeight;
+
+impl<N, R> PathCost<N, R> for UniformWeight {
+    fn path_cost(&self, _from: &N, _to: &N, _relation: &R) -> f32 {
+        1.0
+    }
+}
+
+/// A partition of the nodes of a graph into communities, identified by ids `0..count()`.
+#[derive(Debug, Clone, Default)]
+pub struct Communities {
+    /// The community id of each node.
+    community_of: HashMap<NodeAddress, usize>,
+    /// The nodes of each community, by community id.
+    members: Vec<Vec<NodeAddress>>,
+    /// The modularity of the partition.
+    modularity: f32,
+}
+
+impl Communities {
+    /// Gets the number of communities.
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Gets the id of the community containing the specified node,
+    /// or `None` if the node is not part of the graph.
+    pub fn community_of(&self, address: &NodeAddress) -> Option<usize> {
+        self.community_of.get(address).copied()
+    }
+
+    /// Gets the nodes of the community with the specified id.
+    pub fn members(&self, community: usize) -> Option<&[NodeAddress]> {
+        self.members.get(community).map(Vec::as_slice)
+    }
+
+    /// Iterates the nodes of all communities, in order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = &[NodeAddress]> {
+        self.members.iter().map(Vec::as_slice)
+    }
+
+    /// Gets the modularity of the partition, ranging from `-0.5` to `1.0`.
+    /// Higher values denote denser connections within communities than between them.
+    pub fn modularity(&self) -> f32 {
+        self.modularity
+    }
+
+    /// Builds the communities from the community of each node of the network.
+    fn new(nodes: Vec<NodeAddress>, network: &Network, assignment: &[usize]) -> Self {
+        let assignment = renumber(assignment);
+        let mut communities = Communities {
+            modularity: network.modularity(&assignment) as f32,
+            ..Default::default()
+        };
+        for (address, community) in nodes.into_iter().zip(assignment) {
+            if community == communities.members.len() {
+                communities.members.push(Vec::new());
+            }
+            communities.members[community].push(address.clone());
+            communities.community_of.insert(address, community);
+        }
+        communities
+    }
+}
+
+/// Detects communities using the Louvain method.
+///
+/// Nodes are repeatedly moved to the neighboring community that increases the modularity
+/// the most; the resulting communities are then merged into single nodes and the process
+/// is repeated until the modularity no longer improves.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn louvain<G, P>(graph: &G, weight: &P) -> Result<Communities, NodeAddressError>
+where
+    G: Graph,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let (nodes, network) = Network::build(graph, weight)?;
+
+    // The community of each node of the graph, i.e. of the aggregated node containing it.
+    let mut assignment: Vec<_> = (0..nodes.len()).collect();
+    let mut aggregated = network.clone();
+    while let Some(moved) = aggregated.move_nodes() {
+        for community in &mut assignment {
+            *community = moved[*community];
+        }
+        aggregated = aggregated.aggregate(&moved);
+    }
+
+    Ok(Communities::new(nodes, &network, &assignment))
+}
+
+/// Detects communities using label propagation.
+///
+/// Every node starts out with its own label and repeatedly adopts the label carrying the
+/// largest total weight among its neighbors until no label changes. Ties are resolved in
+/// favor of the current label, then the highest label. Nodes sharing a label form a community.
+/// This is faster than [`louvain`], but usually finds partitions of lower modularity.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn label_propagation<G, P>(graph: &G, weight: &P) -> Result<Communities, NodeAddressError>
+where
+    G: Graph,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let (nodes, network) = Network::build(graph, weight)?;
+
+    let mut labels: Vec<_> = (0..nodes.len()).collect();
+    for _ in 0..MAX_PASSES {
+        let mut changed = false;
+        for node in 0..nodes.len() {
+            let mut weights = BTreeMap::new();
+            for &(neighbor, weight) in &network.neighbors[node] {
+                *weights.entry(labels[neighbor]).or_insert(0.0) += weight;
+            }
+
+            // Keep the current label if it is among the best, otherwise pick the highest one.
+            let current = weights.get(&labels[node]).copied().unwrap_or_default();
+            let best =
+                weights
+                    .into_iter()
+                    .fold(
+                        None,
+                        |best: Option<(usize, f64)>, (label, weight)| match best {
+                            Some((_, best_weight)) if best_weight > weight => best,
+                            _ => Some((label, weight)),
+                        },
+                    );
+            if let Some((label, weight)) = best {
+                if weight > current {
+                    labels[node] = label;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(Communities::new(nodes, &network, &labels))
+}
+
+/// An undirected, weighted graph over node indices.
+#[derive(Debug, Clone)]
+struct Network {
+    /// The neighbors of each node and the total weight of the edges to them, excluding self-loops.
+    neighbors: Vec<Vec<(usize, f64)>>,
+    /// The total weight of the self-loops of each node.
+    loops: Vec<f64>,
+    /// The weighted degree of each node, counting self-loops twice.
+    degree: Vec<f64>,
+    /// The total weight of all edges.
+    total: f64,
+}
+
+impl Network {
+    /// Builds the network of a graph, returning it along with the node of each index.
+    fn build<G, P>(graph: &G, weight: &P) -> Result<(Vec<NodeAddress>, Self), NodeAddressError>
+    where
+        G: Graph,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        let nodes: Vec<_> = graph.nodes().map(|(address, _)| address).collect();
+        let index: HashMap<_, _> = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, address)| (address.clone(), index))
+            .collect();
+        let lookup = |address: &NodeAddress| {
+            index
+                .get(address)
+                .copied()
+                .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone()))
+        };
+
+        let mut neighbors = vec![BTreeMap::new(); nodes.len()];
+        let mut loops = vec![0.0; nodes.len()];
+        for (from, relation, to) in graph.edges() {
+            let weight = weight.path_cost(
+                graph.local_node_data_ref(&from)?,
+                graph.local_node_data_ref(&to)?,
+                relation,
+            ) as f64;
+            if !(weight > 0.0 && weight.is_finite()) {
+                continue;
+            }
+
+            let (from, to) = (lookup(&from)?, lookup(&to)?);
+            if from == to {
+                loops[from] += weight;
+            } else {
+                *neighbors[from].entry(to).or_insert(0.0) += weight;
+                *neighbors[to].entry(from).or_insert(0.0) += weight;
+            }
+        }
+
+        Ok((nodes, Self::new(neighbors, loops)))
+    }
+
+    fn new(neighbors: Vec<BTreeMap<usize, f64>>, loops: Vec<f64>) -> Self {
+        let neighbors: Vec<Vec<_>> = neighbors
+            .into_iter()
+            .map(|neighbors| neighbors.into_iter().collect())
+            .collect();
+        let degree: Vec<f64> = neighbors
+            .iter()
+            .zip(&loops)
+            .map(|(neighbors, loops)| neighbors.iter().map(|(_, w)| w).sum::<f64>() + 2.0 * loops)
+            .collect();
+        let total = degree.iter().sum::<f64>() / 2.0;
+        Network {
+            neighbors,
+            loops,
+            degree,
+            total,
+        }
+    }
+
+    /// Determines the modularity of a partition of the network.
+    fn modularity(&self, assignment: &[usize]) -> f64 {
+        if self.total <= 0.0 {
+            return 0.0;
+        }
+
+        let mut internal = vec![0.0; self.degree.len()];
+        let mut degree = vec![0.0; self.degree.len()];
+        for (node, &community) in assignment.iter().enumerate() {
+            degree[community] += self.degree[node];
+            internal[community] += self.loops[node];
+            for &(neighbor, weight) in &self.neighbors[node] {
+                if assignment[neighbor] == community {
+                    // Every edge is seen from both of its ends.
+                    internal[community] += weight / 2.0;
+                }
+            }
+        }
+
+        internal
+            .iter()
+            .zip(&degree)
+            .map(|(internal, degree)| internal / self.total - (degree / (2.0 * self.total)).powi(2))
+            .sum()
+    }
+
+    /// Moves nodes between communities for as long as the modularity improves.
+    ///
+    /// Returns the renumbered community of each node, or `None` if no node was moved.
+    fn move_nodes(&self) -> Option<Vec<usize>> {
+        let mut community: Vec<_> = (0..self.degree.len()).collect();
+        let mut community_degree = self.degree.clone();
+        let mut moved = false;
+
+        // Scratch space for the neighboring communities of a node, reset after each node.
+        let mut weight_to: Vec<Option<f64>> = vec![None; self.degree.len()];
+        let mut candidates = Vec::new();
+
+        let mut improved = self.total > 0.0;
+        while improved {
+            improved = false;
+            for node in 0..community.len() {
+                let current = community[node];
+                let degree = self.degree[node];
+                community_degree[current] -= degree;
+
+                // The weight of the edges to each neighboring community, indexed by community,
+                // and the neighboring communities in order of discovery.
+                candidates.push(current);
+                weight_to[current] = Some(0.0);
+                for &(neighbor, weight) in &self.neighbors[node] {
+                    let neighbor_community = community[neighbor];
+                    match &mut weight_to[neighbor_community] {
+                        Some(total) => *total += weight,
+                        entry @ None => {
+                            *entry = Some(weight);
+                            candidates.push(neighbor_community);
+                        }
+                    }
+                }
+
+                let gain = |candidate: usize, weight: f64| {
+                    weight - community_degree[candidate] * degree / (2.0 * self.total)
+                };
+                let mut best = (current, 0.0);
+                for (index, candidate) in candidates.drain(..).enumerate() {
+                    let weight = weight_to[candidate].take().unwrap_or_default();
+                    let candidate_gain = gain(candidate, weight);
+                    if index == 0 || candidate_gain > best.1 + MIN_GAIN {
+                        best = (candidate, candidate_gain);
+                    }
+                }
+
+                community_degree[best.0] += degree;
+                if best.0 != current {
+                    community[node] = best.0;
+                    improved = true;
+                    moved = true;
+                }
+            }
+        }
+
+        moved.then(|| renumber(&community))
+    }
+
+    /// Merges the nodes of each community into a single node.
+    fn aggregate(&self, community: &[usize]) -> Self {
+        let count = community.iter().max().map_or(0, |max| max + 1);
+        let mut neighbors = vec![BTreeMap::new(); count];
+        let mut loops = vec![0.0; count];
+        for (node, &from) in community.iter().enumerate() {
+            loops[from] += self.loops[node];
+            for &(neighbor, weight) in &self.neighbors[node] {
+                let to = community[neighbor];
+                if from == to {
+                    // Every edge is seen from both of its ends.
+                    loops[from] += weight / 2.0;
+                } else {
+                    *neighbors[from].entry(to).or_insert(0.0) += weight;
+                }
+            }
+        }
+        Self::new(neighbors, loops)
+    }
+}
+
+/// Renumbers communities to `0..count` in the order of their first node.
+fn renumber(assignment: &[usize]) -> Vec<usize> {
+    let mut ids = HashMap::new();
+    assignment
+        .iter()
+        .map(|community| {
+            let next = ids.len();
+            *ids.entry(*community).or_insert(next)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::london_graph;
+    #[cfg(feature = "examples-movies")]
+    use crate::examples::movie_graph::movie_graph;
+    use crate::test_support::RelationValue;
+
+    #[test]
+    fn it_works() {
+        // Two triangles a-b-c and d-e-f, connected by c-d.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d, e, f] = ["a", "b", "c", "d", "e", "f"].map(|name| graph.add(name));
+        for (from, to) in [
+            (&a, &b),
+            (&b, &c),
+            (&c, &a),
+            (&c, &d),
+            (&d, &e),
+            (&e, &f),
+            (&f, &d),
+        ] {
+            graph.link_to(from, to, ()).unwrap();
+        }
+
+        for communities in [
+            louvain(&graph, &UniformWeight).unwrap(),
+            label_propagation(&graph, &UniformWeight).unwrap(),
+        ] {
+            assert_eq!(communities.count(), 2);
+            assert_eq!(
+                communities.members(0),
+                Some(&[a.clone(), b.clone(), c.clone()][..])
+            );
+            assert_eq!(communities.community_of(&f), Some(1));
+            assert!((communities.modularity() - (6.0 / 7.0 - 0.5)).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        // A path a-b-c-d whose middle relation is strong.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, 1.0).unwrap();
+        graph.link_to(&b, &c, 10.0).unwrap();
+        graph.link_to(&c, &d, 1.0).unwrap();
+
+        let weighted = louvain(&graph, &RelationValue).unwrap();
+        assert_eq!(weighted.community_of(&b), weighted.community_of(&c));
+
+        let uniform = louvain(&graph, &UniformWeight).unwrap();
+        assert_eq!(uniform.community_of(&a), uniform.community_of(&b));
+        assert_ne!(uniform.community_of(&b), uniform.community_of(&c));
+        assert_eq!(uniform.community_of(&c), uniform.community_of(&d));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-movies")]
+    fn collaboration_clusters() {
+        let graph = movie_graph();
+        let louvain = louvain(&graph, &UniformWeight).unwrap();
+        let propagation = label_propagation(&graph, &UniformWeight).unwrap();
+        for communities in [&louvain, &propagation] {
+            let sizes: usize = communities.iter().map(<[_]>::len).sum();
+            assert_eq!(sizes, graph.node_count());
+            assert!(communities.count() > 1);
+        }
+        assert!(louvain.modularity() > 0.5);
+        assert!(louvain.modularity() >= propagation.modularity());
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn london_districts() {
+        let graph = london_graph();
+        let communities = louvain(&graph, &UniformWeight).unwrap();
+        assert!(communities.modularity() > 0.5);
+
+        // The unconnected stations 5 and 200 form communities of their own.
+        for island in [
+            NodeAddress::from_local(graph.id(), 4),
+            NodeAddress::from_local(graph.id(), 199),
+        ] {
+            let community = communities.community_of(&island).unwrap();
+            assert_eq!(
+                communities.members(community),
+                Some(std::slice::from_ref(&island))
+            );
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Provides weakly and strongly connected components.
+//!
+//! Both analyses run in time linear in the number of nodes and relations and assign
+//! a component id to every node of the graph, which makes them suitable for finding
+//! unreachable islands without probing pairs of nodes with path queries.
+
+use crate::embedded_property_graph::EmbeddedPropertyGraph;
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::NodeAddress;
+use crate::traversal::{depth_first_visit, Control, Visitor};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// A partition of the nodes of a graph into components, identified by ids `0..count()`.
+#[derive(Debug, Clone, Default)]
+pub struct Components {
+    /// The component id of each node.
+    component_of: HashMap<NodeAddress, usize>,
+    /// The nodes of each component, by component id.
+    members: Vec<Vec<NodeAddress>>,
+}
+
+impl Components {
+    /// Gets the number of components.
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Gets the id of the component containing the specified node,
+    /// or `None` if the node is not part of the graph.
+    pub fn component_of(&self, address: &NodeAddress) -> Option<usize> {
+        self.component_of.get(address).copied()
+    }
+
+    /// Gets the nodes of the component with the specified id.
+    pub fn members(&self, component: usize) -> Option<&[NodeAddress]> {
+        self.members.get(component).map(Vec::as_slice)
+    }
+
+    /// Iterates the nodes of all components, in order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = &[NodeAddress]> {
+        self.members.iter().map(Vec::as_slice)
+    }
+
+    /// Builds the condensation of the graph the components were determined for.
+    ///
+    /// The condensation has one node per component, holding the component's members,
+    /// and a single relation between two components wherever the original graph has
+    /// at least one relation between their members. The node of component `i` is the
+    /// `i`-th node added to the condensation. For strongly connected components,
+    /// the condensation is a directed acyclic graph.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `graph` contains relations to nodes that are not part of the components.
+    pub fn condensation<G>(
+        &self,
+        graph: &G,
+    ) -> Result<EmbeddedPropertyGraph<Vec<NodeAddress>, ()>, NodeAddressError>
+    where
+        G: Graph,
+    {
+        let mut condensation = EmbeddedPropertyGraph::default();
+        let addresses: Vec<_> = self
+            .members
+            .iter()
+            .map(|members| condensation.add(members.clone()))
+            .collect();
+
+        let mut linked = HashSet::new();
+        for (from, _, to) in graph.edges() {
+            let from = self.lookup(&from)?;
+            let to = self.lookup(&to)?;
+            if from != to && linked.insert((from, to)) {
+                condensation.link_to(&addresses[from], &addresses[to], ())?;
+            }
+        }
+
+        Ok(condensation)
+    }
+
+    /// Gets the component id of a node, failing if the node is unknown.
+    fn lookup(&self, address: &NodeAddress) -> Result<usize, NodeAddressError> {
+        self.component_of(address)
+            .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone()))
+    }
+
+    /// Adds a new component with the specified members.
+    fn push(&mut self, members: Vec<NodeAddress>) {
+        let component = self.members.len();
+        for address in &members {
+            self.component_of.insert(address.clone(), component);
+        }
+        self.members.push(members);
+    }
+}
+
+/// Determines the weakly connected components of a graph, i.e. the components
+/// that are connected when ignoring the direction of relations.
+///
+/// Components are numbered in the order in which the graph yields their first node.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn weakly_connected_components<G>(graph: &G) -> Result<Components, NodeAddressError>
+where
+    G: Graph,
+{
+    let mut components = Components::default();
+
+    for (start, _) in graph.nodes() {
+        if components.component_of.contains_key(&start) {
+            continue;
+        }
+
+        let mut members = vec![start.clone()];
+        let mut discovered = HashSet::from([start.clone()]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(current_addr) = queue.pop_front() {
+            for neighbor in graph.iter_local_neighbors_directed(&current_addr, Direction::Both)? {
+                if discovered.insert(neighbor.address.clone()) {
+                    members.push(neighbor.address.clone());
+                    queue.push_back(neighbor.address.clone());
+                }
+            }
+        }
+
+        components.push(members);
+    }
+
+    Ok(components)
+}
+
+/// Determines the strongly connected components of a graph using Tarjan's algorithm.
+///
+/// Within a strongly connected component, every node can be reached from every other node.
+/// Components are numbered in topological order of the condensation: relations between
+/// different components always lead from a lower to a higher component id.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn strongly_connected_components<G>(graph: &G) -> Result<Components, NodeAddressError>
+where
+    G: Graph,
+{
+    let mut tarjan = Tarjan::default();
+    depth_first_visit(
+        graph,
+        graph.nodes().map(|(address, _)| address),
+        &mut tarjan,
+    )?;
+
+    // Tarjan's algorithm completes components in reverse topological order.
+    let mut components = Components::default();
+    for members in tarjan.completed.into_iter().rev() {
+        components.push(members);
+    }
+    Ok(components)
+}
+
+/// The bookkeeping of Tarjan's algorithm, driven by a depth-first traversal.
+#[derive(Default)]
+struct Tarjan {
+    /// The order in which each node was discovered.
+    index: HashMap<NodeAddress, usize>,
+    /// The lowest discovery index reachable from each node's subtree.
+    low_link: HashMap<NodeAddress, usize>,
+    /// The parent of each node in the depth-first search tree.
+    parent: HashMap<NodeAddress, NodeAddress>,
+    /// The nodes whose component is not yet complete, and the set of them.
+    stack: Vec<NodeAddress>,
+    on_stack: HashSet<NodeAddress>,
+    /// The completed components.
+    completed: Vec<Vec<NodeAddress>>,
+}
+
+impl Tarjan {
+    /// Lowers the low-link of `from` if `to` belongs to a component that is not yet complete.
+    fn update(&mut self, from: &NodeAddress, to: &NodeAddress) {
+        if self.on_stack.contains(to) {
+            let index = self.index[to];
+            let low_link = self.low_link.get_mut(from).expect("node was discovered");
+            *low_link = (*low_link).min(index);
+        }
+    }
+}
+
+impl<R> Visitor<R> for Tarjan {
+    fn discover(&mut self, node: &NodeAddress) -> Control {
+        let index = self.index.len();
+        self.index.insert(node.clone(), index);
+        self.low_link.insert(node.clone(), index);
+        self.stack.push(node.clone());
+        self.on_stack.insert(node.clone());
+        Control::Continue
+    }
+
+    fn tree_edge(&mut self, from: &NodeAddress, _relation: &R, to: &NodeAddress) -> Control {
+        self.parent.insert(to.clone(), from.clone());
+        Control::Continue
+    }
+
+    fn back_edge(&mut self, from: &NodeAddress, _relation: &R, to: &NodeAddress) -> Control {
+        self.update(from, to);
+        Control::Continue
+    }
+
+    fn cross_edge(&mut self, from: &NodeAddress, _relation: &R, to: &NodeAddress) -> Control {
+        self.update(from, to);
+        Control::Continue
+    }
+
+    fn finish(&mut self, node: &NodeAddress) -> Control {
+        let low_link = self.low_link[node];
+
+        // The node is the root of a component; everything above it on the stack belongs to it.
+        if low_link == self.index[node] {
+            let position = self
+                .stack
+                .iter()
+                .rposition(|address| address == node)
+                .expect("node is on the stack");
+            let members = self.stack.split_off(position);
+            for address in &members {
+                self.on_stack.remove(address);
+            }
+            self.completed.push(members);
+        }
+
+        if let Some(parent) = self.parent.get(node) {
+            let parent_low_link = self
+                .low_link
+                .get_mut(parent)
+                .expect("parent was discovered");
+            *parent_low_link = (*parent_low_link).min(low_link);
+        }
+        Control::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::london_graph;
+
+    #[test]
+    fn strongly_connected() {
+        // Two cycles a <-> b and c -> d -> e -> c, connected by b -> c, plus a lone node f.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d, e, f] = ["a", "b", "c", "d", "e", "f"].map(|name| graph.add(name));
+        graph.link_bidir(&a, &b, ()).unwrap();
+        graph.link_to(&b, &c, ()).unwrap();
+        graph.link_to(&c, &d, ()).unwrap();
+        graph.link_to(&d, &e, ()).unwrap();
+        graph.link_to(&e, &c, ()).unwrap();
+
+        let components = strongly_connected_components(&graph).unwrap();
+        assert_eq!(components.count(), 3);
+        assert_eq!(components.component_of(&a), components.component_of(&b));
+        assert_eq!(components.component_of(&c), components.component_of(&e));
+        assert_ne!(components.component_of(&a), components.component_of(&c));
+        assert!(components.component_of(&a) < components.component_of(&c));
+        assert_eq!(
+            components.members(components.component_of(&f).unwrap()),
+            Some(&[f][..])
+        );
+
+        let condensation = components.condensation(&graph).unwrap();
+        assert_eq!(condensation.node_count(), 3);
+        assert_eq!(condensation.edge_count(), 1);
+
+        let weak = weakly_connected_components(&graph).unwrap();
+        assert_eq!(weak.count(), 2);
+        assert_eq!(weak.members(0).unwrap().len(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn london_islands() {
+        // Stations 5 and 200 have no connections at all.
+        let graph = london_graph();
+        let islands = [
+            NodeAddress::from_local(graph.id(), 4),
+            NodeAddress::from_local(graph.id(), 199),
+        ];
+
+        let weak = weakly_connected_components(&graph).unwrap();
+        assert_eq!(weak.count(), 3);
+        let singletons: Vec<_> = weak
+            .iter()
+            .filter(|members| members.len() == 1)
+            .flatten()
+            .cloned()
+            .collect();
+        assert_eq!(singletons, islands);
+
+        let strong = strongly_connected_components(&graph).unwrap();
+        let sizes: usize = strong.iter().map(<[_]>::len).sum();
+        assert_eq!(sizes, 200);
+        for island in &islands {
+            assert_eq!(
+                strong.members(strong.component_of(island).unwrap()),
+                Some(std::slice::from_ref(island))
+            );
+        }
+
+        // Every relation between components leads to a higher component id.
+        let condensation = strong.condensation(&graph).unwrap();
+        for (from, _, to) in condensation.edges() {
+            assert!(
+                strong.component_of(&condensation.node(&from).unwrap()[0])
+                    < strong.component_of(&condensation.node(&to).unwrap()[0])
+            );
+        }
+        assert_eq!(
+            strongly_connected_components(&condensation)
+                .unwrap()
+                .count(),
+            condensation.node_count()
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! Provides an immutable graph type using a compressed sparse row (CSR) layout.
+
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::{GraphId, NodeAddress};
+use crate::node_relation::{NodeRelation, NodeRelationRef};
+
+/// An immutable graph type storing all relations in contiguous arrays.
+///
+/// The relations of all nodes are stored back to back, ordered by their source node;
+/// the outgoing relations of the node in slot `i` occupy the range `offsets[i]..offsets[i + 1]`
+/// of the `targets` and `relations` arrays. Incoming relations are indexed the same way.
+///
+/// A [`CsrGraph`] is obtained by freezing a mutable graph, e.g. through
+/// [`PropertyGraph::freeze`](crate::property_graph::PropertyGraph::freeze) or
+/// [`EmbeddedPropertyGraph::freeze`](crate::embedded_property_graph::EmbeddedPropertyGraph::freeze).
+/// Node addresses of the original graph stay valid.
+#[derive(Debug)]
+pub struct CsrGraph<T, R> {
+    /// The id of the original graph.
+    graph: GraphId,
+    /// The node data by slot index; `None` for slots of removed nodes.
+    nodes: Vec<Option<T>>,
+    /// The generation of each slot.
+    generations: Vec<u32>,
+    /// The start of each node's outgoing relations; has one more entry than there are slots.
+    offsets: Vec<usize>,
+    /// The target node of each relation.
+    targets: Vec<NodeAddress>,
+    /// The payload of each relation.
+    relations: Vec<R>,
+    /// The start of each node's incoming relations; has one more entry than there are slots.
+    incoming_offsets: Vec<usize>,
+    /// The source node of each incoming relation.
+    sources: Vec<NodeAddress>,
+    /// The index of each incoming relation in the `relations` array.
+    incoming_relations: Vec<usize>,
+}
+
+impl<T, R> CsrGraph<T, R> {
+    /// Builds the graph from the generation, node data and outgoing relations of each slot, in slot order.
+    ///
+    /// The `graph` id is the one of the original graph, whose addresses stay valid.
+    pub(crate) fn from_slots<I>(graph: GraphId, slots: I) -> Self
+    where
+        I: IntoIterator<Item = (u32, Option<(T, Vec<NodeRelation<R>>)>)>,
+    {
+        let slots = slots.into_iter();
+        let (capacity, _) = slots.size_hint();
+        let mut nodes = Vec::with_capacity(capacity);
+        let mut generations = Vec::with_capacity(capacity);
+        let mut offsets = Vec::with_capacity(capacity + 1);
+        let mut targets = Vec::new();
+        let mut relations = Vec::new();
+
+        for (generation, node) in slots {
+            offsets.push(targets.len());
+            generations.push(generation);
+            match node {
+                Some((data, outgoing)) => {
+                    nodes.push(Some(data));
+                    for relation in outgoing {
+                        targets.push(relation.address);
+                        relations.push(relation.relation);
+                    }
+                }
+                None => nodes.push(None),
+            }
+        }
+        offsets.push(targets.len());
+
+        // Counting sort of all relations by their target slot.
+        let mut incoming_offsets = vec![0; nodes.len() + 1];
+        for target in &targets {
+            incoming_offsets[slot_index(target) + 1] += 1;
+        }
+        for index in 1..incoming_offsets.len() {
+            incoming_offsets[index] += incoming_offsets[index - 1];
+        }
+
+        let mut next = incoming_offsets.clone();
+        let mut sources = vec![NodeAddress::from_local(graph, 0); targets.len()];
+        let mut incoming_relations = vec![0; targets.len()];
+        for (source, range) in offsets.windows(2).enumerate() {
+            let source = NodeAddress::from_local_generation(graph, source, generations[source]);
+            for edge in range[0]..range[1] {
+                let position = &mut next[slot_index(&targets[edge])];
+                sources[*position] = source.clone();
+                incoming_relations[*position] = edge;
+                *position += 1;
+            }
+        }
+
+        CsrGraph {
+            graph,
+            nodes,
+            generations,
+            offsets,
+            targets,
+            relations,
+            incoming_offsets,
+            sources,
+            incoming_relations,
+        }
+    }
+
+    /// Gets the id of the original graph, which is part of every address of the graph.
+    pub fn id(&self) -> GraphId {
+        self.graph
+    }
+
+    /// Resolves an address to its slot index, provided the node exists.
+    fn index_of(&self, address: &NodeAddress) -> Result<usize, NodeAddressError> {
+        match address {
+            NodeAddress::Local { graph, .. } if *graph != self.graph => {
+                Err(NodeAddressError::ForeignGraph(address.clone()))
+            }
+            NodeAddress::Local {
+                index, generation, ..
+            } => match self.nodes.get(*index) {
+                Some(Some(_)) if self.generations[*index] == *generation => Ok(*index),
+                Some(_) => Err(NodeAddressError::NodeRemoved(address.clone())),
+                None => Err(NodeAddressError::NodeNotFound(address.clone())),
+            },
+        }
+    }
+}
+
+impl<T, R> Graph for CsrGraph<T, R> {
+    type Node = T;
+    type Relation = R;
+
+    fn iter_local_neighbors(
+        &self,
+        address: &NodeAddress,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        let index = self.index_of(address)?;
+        let range = self.offsets[index]..self.offsets[index + 1];
+        Ok(self.targets[range.clone()]
+            .iter()
+            .zip(&self.relations[range])
+            .map(|(address, relation)| NodeRelationRef { relation, address }))
+    }
+
+    fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        let outgoing = self.iter_local_neighbors(address)?;
+        let index = self.index_of(address)?;
+        let range = self.incoming_offsets[index]..self.incoming_offsets[index + 1];
+
+        let outgoing = direction.includes_outgoing().then_some(outgoing);
+        let incoming = direction.includes_incoming().then(|| {
+            self.sources[range.clone()]
+                .iter()
+                .zip(&self.incoming_relations[range])
+                .map(|(address, &edge)| NodeRelationRef {
+                    relation: &self.relations[edge],
+                    address,
+                })
+        });
+
+        Ok(outgoing
+            .into_iter()
+            .flatten()
+            .chain(incoming.into_iter().flatten()))
+    }
+
+    fn local_node_data_ref(&self, address: &NodeAddress) -> Result<&T, NodeAddressError> {
+        let index = self.index_of(address)?;
+        Ok(self.nodes[index]
+            .as_ref()
+            .expect("slot was checked to be occupied"))
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &T)> {
+        self.nodes.iter().enumerate().filter_map(|(index, node)| {
+            let address =
+                NodeAddress::from_local_generation(self.graph, index, self.generations[index]);
+            node.as_ref().map(|node| (address, node))
+        })
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (NodeAddress, &R, NodeAddress)> {
+        self.offsets
+            .windows(2)
+            .enumerate()
+            .flat_map(move |(index, range)| {
+                let from =
+                    NodeAddress::from_local_generation(self.graph, index, self.generations[index]);
+                (range[0]..range[1]).map(move |edge| {
+                    (
+                        from.clone(),
+                        &self.relations[edge],
+                        self.targets[edge].clone(),
+                    )
+                })
+            })
+    }
+
+    fn id(&self) -> GraphId {
+        CsrGraph::id(self)
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes.iter().filter(|node| node.is_some()).count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.relations.len()
+    }
+}
+
+fn slot_index(address: &NodeAddress) -> usize {
+    match address {
+        NodeAddress::Local { index, .. } => *index,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::*;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::astar::AStarSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bfs::BreadthFirstSearch;
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_paths_as_unfrozen() {
+        // Each graph has its own id, so the paths are compared by slot index.
+        let unfrozen = london_graph();
+        let expected: Vec<_> = BreadthFirstSearch
+            .shortest_path(
+                &unfrozen,
+                NodeAddress::from_local(unfrozen.id(), 0),
+                NodeAddress::from_local(unfrozen.id(), 198),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|link| (slot_index(&link.address), link.relation))
+            .collect();
+
+        let embedded = london_graph().freeze();
+        let property = london_property_graph().freeze();
+        for graph in [&embedded, &property] {
+            let start = NodeAddress::from_local(graph.id(), 0);
+            let target = NodeAddress::from_local(graph.id(), 198);
+            let path = BreadthFirstSearch
+                .shortest_path(graph, start.clone(), target.clone())
+                .unwrap();
+            assert!(path
+                .into_iter()
+                .map(|link| (slot_index(&link.address), link.relation))
+                .eq(expected.iter().cloned()));
+
+            let path = AStarSearch
+                .shortest_path(
+                    graph,
+                    start.clone(),
+                    target.clone(),
+                    &LondonGraphDistanceCost,
+                    &LondonGraphDistanceHeuristic,
+                )
+                .unwrap();
+            assert_eq!(path.len(), 10);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_nodes_and_edges_as_unfrozen() {
+        let graph = london_graph();
+        let nodes: Vec<_> = graph.nodes().map(|(address, _)| address).collect();
+        let edges: Vec<_> = graph
+            .edges()
+            .map(|(from, relation, to)| (from, *relation, to))
+            .collect();
+
+        let frozen = graph.freeze();
+        assert_eq!(frozen.node_count(), 200);
+        assert_eq!(frozen.edge_count(), edges.len());
+        assert!(frozen.nodes().map(|(address, _)| address).eq(nodes));
+        assert!(frozen
+            .edges()
+            .map(|(from, relation, to)| (from, *relation, to))
+            .eq(edges));
+    }
+
+    #[test]
+    fn removed_nodes_and_incoming() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        let c = graph.add("c");
+        graph.link_to(&a, &c, "ac").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+        graph.link_to(&c, &a, "ca").unwrap();
+        graph.remove(&b).unwrap();
+
+        let graph = graph.freeze();
+        assert!(matches!(
+            graph.local_node_data_ref(&b),
+            Err(NodeAddressError::NodeRemoved(_))
+        ));
+        assert_eq!(*graph.local_node_data_ref(&c).unwrap(), "c");
+
+        let incoming: Vec<_> = graph
+            .iter_local_neighbors_directed(&c, Direction::Incoming)
+            .unwrap()
+            .map(|relation| (*relation.relation, relation.address.clone()))
+            .collect();
+        assert_eq!(incoming, [("ac", a.clone())]);
+
+        let outgoing: Vec<_> = graph
+            .iter_local_neighbors(&c)
+            .unwrap()
+            .map(|relation| *relation.relation)
+            .collect();
+        assert_eq!(outgoing, ["ca"]);
+    }
+
+    #[test]
+    fn foreign_address() {
+        let mut a = EmbeddedPropertyGraph::<_, ()>::default();
+        let mut b = EmbeddedPropertyGraph::<_, ()>::default();
+        let address = a.add("a");
+        b.add("b");
+
+        let a = a.freeze();
+        let b = b.freeze();
+        assert_eq!(*a.local_node_data_ref(&address).unwrap(), "a");
+        assert!(matches!(
+            b.local_node_data_ref(&address),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert!(matches!(
+            b.iter_local_neighbors(&address),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+    }
+}
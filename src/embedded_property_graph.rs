@@ -1,23 +1,41 @@
 //! Provides a property graph type that embeds node relations into the node itself.
 
+use crate::csr_graph::CsrGraph;
 use crate::errors::NodeAddressError;
-use crate::node_address::NodeAddress;
-use crate::node_relation::NodeRelation;
+use crate::graph::{Direction, Graph};
+use crate::node_address::{GraphId, NodeAddress};
+use crate::node_relation::{
+    remove_relations, update_incoming, IncomingRelation, NodeRelation, NodeRelationRef,
+};
+use crate::node_slots::NodeSlots;
 use std::borrow::Borrow;
+use std::collections::HashSet;
 use std::ops::{Deref, DerefMut};
 
 /// A property graph type that embeds node relations into the node itself.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EmbeddedPropertyGraph<T, R> {
-    nodes: Vec<Node<T, R>>,
+    nodes: NodeSlots<Node<T, R>>,
 }
 
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[cfg(feature = "boxed-nodes")]
 struct Node<T, R>(Box<NodeData<T, R>>);
 
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[cfg(not(feature = "boxed-nodes"))]
-struct Node<T>(NodeData<T>);
+struct Node<T, R>(NodeData<T, R>);
 
 impl<T, R> From<NodeData<T, R>> for Node<T, R> {
     fn from(value: NodeData<T, R>) -> Self {
@@ -32,6 +50,19 @@ impl<T, R> From<NodeData<T, R>> for Node<T, R> {
     }
 }
 
+impl<T, R> Node<T, R> {
+    fn into_inner(self) -> NodeData<T, R> {
+        #[cfg(feature = "boxed-nodes")]
+        {
+            *self.0
+        }
+        #[cfg(not(feature = "boxed-nodes"))]
+        {
+            self.0
+        }
+    }
+}
+
 impl<T, R> Deref for Node<T, R> {
     type Target = NodeData<T, R>;
 
@@ -47,24 +78,76 @@ impl<T, R> DerefMut for Node<T, R> {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(dead_code)]
 pub(crate) struct NodeData<T, R> {
     pub(crate) id: usize,
     pub(crate) data: T,
     // TODO: Use array-backed lists for this? Depends on the connectivity.
     pub(crate) outgoing: Vec<NodeRelation<R>>,
+    /// Back-references to all relations leading to this node.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) incoming: Vec<IncomingRelation>,
 }
 
 impl<T, R> EmbeddedPropertyGraph<T, R> {
     pub fn add(&mut self, data: T) -> NodeAddress {
-        let id = self.nodes.len();
-        let node = NodeData {
-            id,
-            data,
-            outgoing: Vec::new(),
-        };
-        self.nodes.push(node.into());
-        NodeAddress::from_local(id)
+        self.nodes.insert_with(|id| {
+            NodeData {
+                id,
+                data,
+                outgoing: Vec::new(),
+                incoming: Vec::new(),
+            }
+            .into()
+        })
+    }
+
+    /// Removes the node at the specified address, together with all relations from and to it.
+    ///
+    /// The slot of the removed node is recycled by later calls to [`add`](Self::add);
+    /// the removed address itself stays invalid and fails with [`NodeAddressError::NodeRemoved`].
+    ///
+    /// ## Returns
+    /// The data of the removed node.
+    pub fn remove(&mut self, address: &NodeAddress) -> Result<T, NodeAddressError> {
+        let node = self.nodes.remove(address)?;
+        let sources: HashSet<_> = node
+            .incoming
+            .iter()
+            .map(|relation| &relation.source)
+            .collect();
+        for source in sources {
+            // Self-loops refer to the already removed node.
+            if source != address {
+                self.remove_outgoing(source, |relation| relation.address == *address)?;
+            }
+        }
+        for relation in &node.outgoing {
+            if let Ok(target) = self.nodes.get_mut(&relation.address) {
+                target
+                    .incoming
+                    .retain(|relation| relation.source != *address);
+            }
+        }
+        Ok(node.0.data)
+    }
+
+    /// Converts the graph into an immutable [`CsrGraph`] optimized for read access.
+    ///
+    /// All addresses of this graph remain valid in the frozen graph.
+    pub fn freeze(self) -> CsrGraph<T, R> {
+        let graph = self.nodes.graph();
+        CsrGraph::from_slots(
+            graph,
+            self.nodes.into_slots().map(|(generation, node)| {
+                let node = node.map(|node| {
+                    let node = node.into_inner();
+                    (node.data, node.outgoing)
+                });
+                (generation, node)
+            }),
+        )
     }
 
     /// Iterates the neighbors of a node given its [`NodeAddress`] under the condition that the node is locally available.
@@ -72,11 +155,39 @@ impl<T, R> EmbeddedPropertyGraph<T, R> {
         &self,
         address: &NodeAddress,
     ) -> Result<impl Iterator<Item = &NodeRelation<R>>, NodeAddressError> {
-        #[allow(unreachable_patterns)]
-        match address {
-            NodeAddress::Local(id) => Ok(self.nodes[*id].outgoing.iter()),
-            _ => Err(NodeAddressError::NodeNotLocal(address.clone())),
-        }
+        Ok(self.nodes.get(address)?.outgoing.iter())
+    }
+
+    /// Iterates the neighbors of a node along relations of the specified [`Direction`].
+    ///
+    /// For incoming relations, the yielded address is the one of the node the relation originates from.
+    pub fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        let node = self.nodes.get(address)?;
+
+        let outgoing = direction
+            .includes_outgoing()
+            .then(|| node.outgoing.iter().map(NodeRelationRef::from));
+        let incoming = direction.includes_incoming().then(|| {
+            node.incoming.iter().map(|incoming| {
+                let source = self
+                    .nodes
+                    .get(&incoming.source)
+                    .expect("the incoming index refers to a removed node");
+                NodeRelationRef {
+                    relation: &source.outgoing[incoming.position].relation,
+                    address: &incoming.source,
+                }
+            })
+        });
+
+        Ok(outgoing
+            .into_iter()
+            .flatten()
+            .chain(incoming.into_iter().flatten()))
     }
 
     /// Gets a node's data given its [`NodeAddress`] under the condition that the node is locally available.
@@ -84,11 +195,49 @@ impl<T, R> EmbeddedPropertyGraph<T, R> {
         &self,
         address: &NodeAddress,
     ) -> Result<&T, NodeAddressError> {
-        #[allow(unreachable_patterns)]
-        match address {
-            NodeAddress::Local(id) => Ok(&self.nodes[*id].data),
-            _ => Err(NodeAddressError::NodeNotLocal(address.clone())),
-        }
+        Ok(&self.nodes.get(address)?.data)
+    }
+
+    /// Gets a node's data given its [`NodeAddress`].
+    pub fn node(&self, address: &NodeAddress) -> Result<&T, NodeAddressError> {
+        self.local_node_data_ref(address)
+    }
+
+    /// Gets a node's data given its [`NodeAddress`] for mutation.
+    pub fn node_mut(&mut self, address: &NodeAddress) -> Result<&mut T, NodeAddressError> {
+        Ok(&mut self.nodes.get_mut(address)?.data)
+    }
+
+    /// Iterates all nodes of the graph in slot order, together with their addresses.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &T)> {
+        self.nodes
+            .iter()
+            .map(|(address, node)| (address, &node.data))
+    }
+
+    /// Iterates all relations of the graph as `(from, relation, to)` triples,
+    /// ordered by the `from` node's slot and then by the order they were linked in.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeAddress, &R, NodeAddress)> {
+        self.nodes.iter().flat_map(move |(from, node)| {
+            node.outgoing
+                .iter()
+                .map(move |relation| (from.clone(), &relation.relation, relation.address.clone()))
+        })
+    }
+
+    /// Gets the id of the graph, which is part of every address the graph hands out.
+    pub fn id(&self) -> GraphId {
+        self.nodes.graph()
+    }
+
+    /// Gets the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Gets the number of relations in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.nodes.iter().map(|(_, node)| node.outgoing.len()).sum()
     }
 
     /// Creates a connection between the nodes at the `from` address and the `to` address.
@@ -100,15 +249,30 @@ impl<T, R> EmbeddedPropertyGraph<T, R> {
     /// * `from` - The node from which to link to another node.
     /// * `to` The node to link to.
     /// * `relation` - The type of relation.
-    pub fn link_to<A: Borrow<NodeAddress>>(&mut self, from: A, to: A, relation: R) {
+    ///
+    /// ## Errors
+    /// Fails without modifying the graph if either node does not exist.
+    pub fn link_to<A: Borrow<NodeAddress>>(
+        &mut self,
+        from: A,
+        to: A,
+        relation: R,
+    ) -> Result<(), NodeAddressError> {
         let from = from.borrow();
         let to = to.borrow();
-        match from {
-            NodeAddress::Local(from_idx) => self.nodes[*from_idx].outgoing.push(NodeRelation {
-                relation,
-                address: to.clone(),
-            }),
-        }
+        self.local_node_data_ref(to)?;
+        let outgoing = &mut self.nodes.get_mut(from)?.outgoing;
+        outgoing.push(NodeRelation {
+            relation,
+            address: to.clone(),
+        });
+
+        let position = outgoing.len() - 1;
+        self.nodes.get_mut(to)?.incoming.push(IncomingRelation {
+            source: from.clone(),
+            position,
+        });
+        Ok(())
     }
 
     /// Creates a bidirectional connection between the nodes at the `from` address and the `to` address.
@@ -120,24 +284,251 @@ impl<T, R> EmbeddedPropertyGraph<T, R> {
     /// * `from` - The node from which to link to another node.
     /// * `to` The node to link to.
     /// * `relation` - The type of relation.
-    pub fn link_bidir<A: Borrow<NodeAddress>>(&mut self, from: A, to: A, relation: R)
+    ///
+    /// ## Errors
+    /// Fails without modifying the graph if either node does not exist.
+    pub fn link_bidir<A: Borrow<NodeAddress>>(
+        &mut self,
+        from: A,
+        to: A,
+        relation: R,
+    ) -> Result<(), NodeAddressError>
     where
         R: Clone,
     {
-        self.link_to(from.borrow(), to.borrow(), relation.clone());
-        self.link_to(to.borrow(), from.borrow(), relation);
+        self.link_to(from.borrow(), to.borrow(), relation.clone())?;
+        self.link_to(to.borrow(), from.borrow(), relation)
+    }
+
+    /// Removes connections from the node at the `from` address to the node at the `to` address.
+    ///
+    /// Only relations for which the `predicate` returns `true` are removed;
+    /// pass `|_| true` to remove all connections between the two nodes.
+    ///
+    /// ## Arguments
+    /// * `from` - The node from which the connections originate.
+    /// * `to` The node the connections lead to.
+    /// * `predicate` - Selects the relations to remove.
+    ///
+    /// ## Returns
+    /// The removed relations, in the order they were linked.
+    pub fn unlink<A, F>(
+        &mut self,
+        from: A,
+        to: A,
+        mut predicate: F,
+    ) -> Result<Vec<R>, NodeAddressError>
+    where
+        A: Borrow<NodeAddress>,
+        F: FnMut(&R) -> bool,
+    {
+        let from = from.borrow();
+        let to = to.borrow();
+        self.local_node_data_ref(to)?;
+        self.remove_outgoing(from, |relation| {
+            relation.address == *to && predicate(&relation.relation)
+        })
+    }
+
+    /// Iterates the relations of all connections from the node at the `from` address to the node at the `to` address.
+    pub fn edges_between<'a>(
+        &'a self,
+        from: &NodeAddress,
+        to: &'a NodeAddress,
+    ) -> Result<impl Iterator<Item = &'a R>, NodeAddressError> {
+        self.local_node_data_ref(to)?;
+        Ok(self
+            .iter_local_neighbors(from)?
+            .filter(move |relation| relation.address == *to)
+            .map(|relation| &relation.relation))
+    }
+
+    /// Mutably iterates the relations of all connections from the node at the `from` address to the node at the `to` address.
+    pub fn edges_between_mut<'a>(
+        &'a mut self,
+        from: &NodeAddress,
+        to: &'a NodeAddress,
+    ) -> Result<impl Iterator<Item = &'a mut R>, NodeAddressError> {
+        self.local_node_data_ref(to)?;
+        let outgoing = &mut self.nodes.get_mut(from)?.outgoing;
+        Ok(outgoing
+            .iter_mut()
+            .filter(move |relation| relation.address == *to)
+            .map(|relation| &mut relation.relation))
+    }
+
+    /// Determines whether the node at the `from` address has at least one connection to the node at the `to` address.
+    pub fn has_edge(&self, from: &NodeAddress, to: &NodeAddress) -> Result<bool, NodeAddressError> {
+        Ok(self.edges_between(from, to)?.next().is_some())
+    }
+
+    /// Removes the outgoing relations of the `from` node for which `remove` returns `true`,
+    /// keeping the back-references of the nodes they lead to in sync.
+    ///
+    /// ## Returns
+    /// The removed relations, in the order they were linked.
+    fn remove_outgoing<F>(
+        &mut self,
+        from: &NodeAddress,
+        remove: F,
+    ) -> Result<Vec<R>, NodeAddressError>
+    where
+        F: FnMut(&NodeRelation<R>) -> bool,
+    {
+        let outgoing = &mut self.nodes.get_mut(from)?.outgoing;
+        let (removed, positions) = remove_relations(outgoing, remove);
+
+        // Relations behind the first removed one moved to a new position.
+        let first = positions
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or(positions.len());
+        let targets: HashSet<_> = removed
+            .iter()
+            .chain(&outgoing[first..])
+            .map(|relation| relation.address.clone())
+            .collect();
+        for target in &targets {
+            // Self-loops of a node being removed refer to the already removed node.
+            if let Ok(target) = self.nodes.get_mut(target) {
+                update_incoming(&mut target.incoming, from, &positions);
+            }
+        }
+        Ok(removed
+            .into_iter()
+            .map(|relation| relation.relation)
+            .collect())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, R> EmbeddedPropertyGraph<T, R> {
+    /// Recreates the incoming index of all nodes from their outgoing relations.
+    fn rebuild_incoming(&mut self) -> Result<(), NodeAddressError> {
+        let links: Vec<_> = self
+            .nodes
+            .iter()
+            .flat_map(|(from, node)| {
+                node.outgoing
+                    .iter()
+                    .enumerate()
+                    .map(move |(position, relation)| {
+                        (from.clone(), position, relation.address.clone())
+                    })
+            })
+            .collect();
+        for (source, position, to) in links {
+            self.nodes
+                .get_mut(&to)?
+                .incoming
+                .push(IncomingRelation { source, position });
+        }
+        Ok(())
+    }
+
+    /// Moves the relations of all nodes from the `serialized` graph over to this graph.
+    fn rebind_relations(&mut self, serialized: GraphId) -> Result<(), NodeAddressError> {
+        let id = self.id();
+        for index in 0..self.nodes.slot_count() {
+            if let Some(address) = self.nodes.address_of(index) {
+                for relation in &mut self.nodes.get_mut(&address)?.outgoing {
+                    relation.address.rebind(serialized, id)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, R> Graph for EmbeddedPropertyGraph<T, R> {
+    type Node = T;
+    type Relation = R;
+
+    fn iter_local_neighbors(
+        &self,
+        address: &NodeAddress,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        Ok(EmbeddedPropertyGraph::iter_local_neighbors(self, address)?.map(NodeRelationRef::from))
+    }
+
+    fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        EmbeddedPropertyGraph::iter_local_neighbors_directed(self, address, direction)
+    }
+
+    fn local_node_data_ref(&self, address: &NodeAddress) -> Result<&T, NodeAddressError> {
+        EmbeddedPropertyGraph::local_node_data_ref(self, address)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &T)> {
+        EmbeddedPropertyGraph::nodes(self)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (NodeAddress, &R, NodeAddress)> {
+        EmbeddedPropertyGraph::edges(self)
+    }
+
+    fn id(&self) -> GraphId {
+        EmbeddedPropertyGraph::id(self)
+    }
+
+    fn node_count(&self) -> usize {
+        EmbeddedPropertyGraph::node_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        EmbeddedPropertyGraph::edge_count(self)
     }
 }
 
 impl<T, R> Default for EmbeddedPropertyGraph<T, R> {
     fn default() -> Self {
-        EmbeddedPropertyGraph { nodes: Vec::new() }
+        EmbeddedPropertyGraph {
+            nodes: NodeSlots::default(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, R> serde::Deserialize<'de> for EmbeddedPropertyGraph<T, R>
+where
+    T: serde::Deserialize<'de>,
+    R: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct Repr<T, R> {
+            nodes: NodeSlots<Node<T, R>>,
+        }
+
+        // The incoming index is not serialized; rebuilding it also validates all relation targets.
+        let Repr { nodes } = Repr::deserialize(deserializer)?;
+        let mut graph = EmbeddedPropertyGraph { nodes };
+
+        // The restored graph receives a fresh id, which its relations are moved over to.
+        let serialized = graph.nodes.renew_graph();
+        graph
+            .rebind_relations(serialized)
+            .map_err(D::Error::custom)?;
+        graph.rebuild_incoming().map_err(D::Error::custom)?;
+        Ok(graph)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    #[cfg(feature = "examples-london")]
     use crate::examples::london_graph::*;
+    #[cfg(feature = "examples-movies")]
     use crate::examples::movie_graph::*;
 
     #[test]
@@ -153,4 +544,293 @@ mod tests {
         let graph = london_graph();
         println!("{:?}", graph);
     }
+
+    #[test]
+    fn remove_node() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        let c = graph.add("c");
+        graph.link_bidir(&a, &b, ()).unwrap();
+        graph.link_bidir(&b, &c, ()).unwrap();
+
+        assert_eq!(graph.remove(&b).unwrap(), "b");
+        assert_eq!(graph.iter_local_neighbors(&a).unwrap().count(), 0);
+        assert_eq!(graph.iter_local_neighbors(&c).unwrap().count(), 0);
+        assert!(matches!(
+            graph.local_node_data_ref(&b),
+            Err(NodeAddressError::NodeRemoved(_))
+        ));
+
+        // The slot is recycled, but the stale address must not resolve to the new node.
+        let d = graph.add("d");
+        assert_ne!(b, d);
+        assert_eq!(*graph.local_node_data_ref(&d).unwrap(), "d");
+        assert!(graph.local_node_data_ref(&b).is_err());
+        assert!(graph.remove(&b).is_err());
+    }
+
+    #[test]
+    fn edges_between_nodes() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        graph.link_to(&a, &b, 10).unwrap();
+        graph.link_to(&a, &b, 20).unwrap();
+        graph.link_to(&b, &a, 30).unwrap();
+
+        assert!(graph.has_edge(&a, &b).unwrap());
+        assert_eq!(
+            graph.edges_between(&a, &b).unwrap().collect::<Vec<_>>(),
+            [&10, &20]
+        );
+
+        for relation in graph.edges_between_mut(&a, &b).unwrap() {
+            *relation += 1;
+        }
+        assert_eq!(
+            graph.edges_between(&a, &b).unwrap().collect::<Vec<_>>(),
+            [&11, &21]
+        );
+
+        assert_eq!(graph.unlink(&a, &b, |&r| r > 20).unwrap(), [21]);
+        assert_eq!(graph.unlink(&a, &b, |_| true).unwrap(), [11]);
+        assert!(!graph.has_edge(&a, &b).unwrap());
+        assert!(graph.has_edge(&b, &a).unwrap());
+    }
+
+    #[test]
+    fn directed_neighbors() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        let c = graph.add("c");
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&c, &b, "cb").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+
+        let incoming: Vec<_> = graph
+            .iter_local_neighbors_directed(&b, Direction::Incoming)
+            .unwrap()
+            .map(|relation| (*relation.relation, relation.address.clone()))
+            .collect();
+        assert_eq!(incoming, [("ab", a.clone()), ("cb", c.clone())]);
+
+        let both: Vec<_> = graph
+            .iter_local_neighbors_directed(&b, Direction::Both)
+            .unwrap()
+            .map(|relation| *relation.relation)
+            .collect();
+        assert_eq!(both, ["bc", "ab", "cb"]);
+
+        graph.unlink(&a, &b, |_| true).unwrap();
+        graph.remove(&c).unwrap();
+        assert_eq!(
+            graph
+                .iter_local_neighbors_directed(&b, Direction::Both)
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn incoming_after_unlink() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c] = ["a", "b", "c"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, "ab1").unwrap();
+        graph.link_to(&a, &c, "ac1").unwrap();
+        graph.link_to(&a, &b, "ab2").unwrap();
+        graph.link_to(&a, &c, "ac2").unwrap();
+
+        let incoming = |graph: &EmbeddedPropertyGraph<&str, &'static str>,
+                        address|
+         -> Vec<(&str, NodeAddress)> {
+            graph
+                .iter_local_neighbors_directed(address, Direction::Incoming)
+                .unwrap()
+                .map(|relation| (*relation.relation, relation.address.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // Removing a relation moves the relations behind it.
+        assert_eq!(
+            graph.unlink(&a, &b, |relation| *relation == "ab1").unwrap(),
+            ["ab1"]
+        );
+        assert_eq!(incoming(&graph, &b), [("ab2", a.clone())]);
+        assert_eq!(
+            incoming(&graph, &c),
+            [("ac1", a.clone()), ("ac2", a.clone())]
+        );
+
+        graph.remove(&b).unwrap();
+        assert_eq!(
+            incoming(&graph, &c),
+            [("ac1", a.clone()), ("ac2", a.clone())]
+        );
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-movies")]
+    fn who_directed_the_matrix() {
+        let graph = movie_graph();
+        let the_matrix = NodeAddress::from_local(graph.id(), 0);
+        let directors = graph
+            .iter_local_neighbors_directed(&the_matrix, Direction::Incoming)
+            .unwrap()
+            .filter(|relation| matches!(relation.relation, RelationType::Directed))
+            .count();
+
+        assert_eq!(directors, 2);
+    }
+
+    #[test]
+    fn unknown_addresses() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        let unknown = NodeAddress::from_local(graph.id(), 42);
+
+        assert!(matches!(
+            graph.local_node_data_ref(&unknown),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+        assert!(matches!(
+            graph.iter_local_neighbors(&unknown),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+        assert!(graph.link_to(&a, &unknown, ()).is_err());
+        assert!(graph.link_to(&unknown, &a, ()).is_err());
+        assert!(graph.has_edge(&a, &unknown).is_err());
+        assert_eq!(graph.iter_local_neighbors(&a).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn foreign_addresses() {
+        // Both graphs have a node in slot 0 of the first generation.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let mut other = EmbeddedPropertyGraph::<_, ()>::default();
+        let a = graph.add("a");
+        let foreign = other.add("b");
+
+        assert!(matches!(
+            graph.local_node_data_ref(&foreign),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert!(matches!(
+            graph.iter_local_neighbors(&foreign),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert!(matches!(
+            graph.link_to(&a, &foreign, ()),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert!(matches!(
+            graph.remove(&foreign),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert_eq!(graph.iter_local_neighbors(&a).unwrap().count(), 0);
+        assert_eq!(*other.node(&foreign).unwrap(), "b");
+    }
+
+    #[test]
+    fn node_and_edge_access() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add(1);
+        let b = graph.add(2);
+        let c = graph.add(3);
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+        graph.link_to(&a, &c, "ac").unwrap();
+
+        *graph.node_mut(&b).unwrap() += 10;
+        assert_eq!(*graph.node(&b).unwrap(), 12);
+
+        graph.remove(&c).unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(
+            graph.nodes().collect::<Vec<_>>(),
+            [(a.clone(), &1), (b.clone(), &12)]
+        );
+        assert_eq!(graph.edges().collect::<Vec<_>>(), [(a, &"ab", b)]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "examples-london"))]
+    fn serde_round_trip() {
+        let mut graph = london_graph();
+        graph
+            .remove(&NodeAddress::from_local(graph.id(), 3))
+            .unwrap();
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let mut restored: EmbeddedPropertyGraph<Station, ConnectionType> =
+            serde_json::from_str(&json).unwrap();
+
+        // The restored graph has an id of its own, but the same nodes in the same slots.
+        assert_ne!(restored.id(), graph.id());
+        let rebind = |address: NodeAddress| match address {
+            NodeAddress::Local {
+                index, generation, ..
+            } => NodeAddress::from_local_generation(restored.id(), index, generation),
+        };
+        assert_eq!(restored.node_count(), graph.node_count());
+        assert!(restored
+            .edges()
+            .eq(graph
+                .edges()
+                .map(|(from, relation, to)| (rebind(from), relation, rebind(to)))));
+        assert!(matches!(
+            restored.node(&NodeAddress::from_local(restored.id(), 3)),
+            Err(NodeAddressError::NodeRemoved(_))
+        ));
+        assert!(matches!(
+            restored.node(&NodeAddress::from_local(graph.id(), 0)),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert_eq!(
+            restored
+                .iter_local_neighbors_directed(
+                    &NodeAddress::from_local(restored.id(), 10),
+                    Direction::Incoming
+                )
+                .unwrap()
+                .count(),
+            graph
+                .iter_local_neighbors_directed(
+                    &NodeAddress::from_local(graph.id(), 10),
+                    Direction::Incoming
+                )
+                .unwrap()
+                .count()
+        );
+
+        // Restoring the same data twice yields two distinct graphs.
+        let again: EmbeddedPropertyGraph<Station, ConnectionType> =
+            serde_json::from_str(&json).unwrap();
+        assert_ne!(again.id(), restored.id());
+
+        // The vacated slot is reused with the next generation.
+        let station = serde_json::from_str(r#"{"id": 201, "x": 0, "y": 0}"#).unwrap();
+        assert_eq!(
+            restored.add(station),
+            NodeAddress::from_local_generation(restored.id(), 3, 1)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_rejects_dangling_relations() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add(1);
+        let b = graph.add(2);
+        graph.link_to(&a, &b, ()).unwrap();
+
+        let json = serde_json::to_string(&graph)
+            .unwrap()
+            .replace(r#""index":1"#, r#""index":7"#);
+        assert!(serde_json::from_str::<EmbeddedPropertyGraph<i32, ()>>(&json).is_err());
+    }
 }
@@ -1,7 +1,50 @@
 use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum NodeAddressError {
     #[error("The specified node address does not represent a local node: {0}")]
     NodeNotLocal(NodeAddress),
+    #[error("The specified node address does not exist in the graph: {0}")]
+    NodeNotFound(NodeAddress),
+    #[error("The node at the specified address was removed: {0}")]
+    NodeRemoved(NodeAddress),
+    #[error("The specified node address belongs to another graph: {0}")]
+    ForeignGraph(NodeAddress),
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TopologicalOrderError<R>
+where
+    R: Clone,
+{
+    #[error("The graph contains a cycle through {} nodes", .0.len() - 1)]
+    Cycle(Vec<NodePathLink<R>>),
+    #[error(transparent)]
+    InvalidNode(#[from] NodeAddressError),
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ShortestPathError<R>
+where
+    R: Clone,
+{
+    #[error("The graph contains a negative cycle through {} nodes", .0.len() - 1)]
+    NegativeCycle(Vec<NodePathLink<R>>),
+    #[error(transparent)]
+    InvalidNode(#[from] NodeAddressError),
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum MaxFlowError {
+    #[error("The source and the sink are the same node: {0}")]
+    SourceIsSink(NodeAddress),
+    #[error("The relation from {from} to {to} has a capacity that is not finite: {capacity}")]
+    InvalidCapacity {
+        from: NodeAddress,
+        to: NodeAddress,
+        capacity: f32,
+    },
+    #[error(transparent)]
+    InvalidNode(#[from] NodeAddressError),
 }
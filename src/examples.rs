@@ -8,7 +8,9 @@ pub mod london_graph;
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "examples-london")]
     use super::london_graph::*;
+    #[cfg(feature = "examples-movies")]
     use super::movie_graph::*;
 
     #[test]
@@ -3,13 +3,16 @@
 #![allow(dead_code)]
 
 use crate::embedded_property_graph::EmbeddedPropertyGraph;
+use crate::errors::NodeAddressError;
 use crate::node_address::NodeAddress;
 use crate::path_queries::{AdmissibleHeuristic, PathCost};
+use crate::property_graph::PropertyGraph;
 
 const MAP_WIDTH: f32 = 760.0;
 const MAP_HEIGHT: f32 = 570.0;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Station {
     /// The station ID
     id: usize,
@@ -20,6 +23,7 @@ pub struct Station {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConnectionType {
     /// A subway line.
     Underground,
@@ -66,9 +70,58 @@ impl PathCost<Station, ConnectionType> for LondonGraphStationsCost {
     }
 }
 
+/// Builds the London map as an [`EmbeddedPropertyGraph`].
 pub fn london_graph() -> EmbeddedPropertyGraph<Station, ConnectionType> {
-    let mut graph = EmbeddedPropertyGraph::default();
+    build(EmbeddedPropertyGraph::default())
+}
+
+/// Builds the London map as a [`PropertyGraph`].
+pub fn london_property_graph() -> PropertyGraph<Station, ConnectionType> {
+    build(PropertyGraph::default())
+}
+
+/// Abstracts over the graph types the map can be built into.
+trait StationGraph {
+    fn add(&mut self, station: Station) -> NodeAddress;
+    fn link_bidir(
+        &mut self,
+        from: &NodeAddress,
+        to: &NodeAddress,
+        connection: ConnectionType,
+    ) -> Result<(), NodeAddressError>;
+}
+
+impl StationGraph for EmbeddedPropertyGraph<Station, ConnectionType> {
+    fn add(&mut self, station: Station) -> NodeAddress {
+        EmbeddedPropertyGraph::add(self, station)
+    }
+
+    fn link_bidir(
+        &mut self,
+        from: &NodeAddress,
+        to: &NodeAddress,
+        connection: ConnectionType,
+    ) -> Result<(), NodeAddressError> {
+        EmbeddedPropertyGraph::link_bidir(self, from, to, connection)
+    }
+}
+
+impl StationGraph for PropertyGraph<Station, ConnectionType> {
+    fn add(&mut self, station: Station) -> NodeAddress {
+        PropertyGraph::add(self, station)
+    }
+
+    fn link_bidir(
+        &mut self,
+        from: &NodeAddress,
+        to: &NodeAddress,
+        connection: ConnectionType,
+    ) -> Result<(), NodeAddressError> {
+        PropertyGraph::link_bidir(self, from, to, connection)
+    }
+}
 
+fn build<G: StationGraph>(mut graph: G) -> G {
     let stations = stations(&mut graph);
 
     connect_taxi(&mut graph, &stations);
@@ -79,7 +132,7 @@ pub fn london_graph() -> EmbeddedPropertyGraph<Station, ConnectionType> {
     graph
 }
 
-fn stations(graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>) -> Vec<NodeAddress> {
+fn stations<G: StationGraph>(graph: &mut G) -> Vec<NodeAddress> {
     #[rustfmt::skip]
     let stations = [
         Station { id: 1, x: 110, y: 32 },
@@ -305,285 +358,285 @@ fn stations(graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>) -> Vec<N
         Station { id: 200, x: 600, y: 600 },
     ];
 
-    return stations
+    stations
         .into_iter()
         .map(|station| graph.add(station))
-        .collect();
+        .collect()
 }
 
 #[rustfmt::skip]
-fn connect_taxi(graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>, stations: &[NodeAddress]) {
-    bidir(graph, &stations, 1, [8, 9], ConnectionType::Taxi);
-    bidir(graph, &stations, 2, [10, 20], ConnectionType::Taxi);
-    bidir(graph, &stations, 3, [4, 11, 12], ConnectionType::Taxi);
-    bidir(graph, &stations, 4, [13], ConnectionType::Taxi);
-    bidir(graph, &stations, 6, [7, 29], ConnectionType::Taxi);
-    bidir(graph, &stations, 7, [17], ConnectionType::Taxi);
-    bidir(graph, &stations, 8, [18, 19], ConnectionType::Taxi);
-    bidir(graph, &stations, 9, [19, 20], ConnectionType::Taxi);
-    bidir(graph, &stations, 10, [11, 21, 34], ConnectionType::Taxi);
-    bidir(graph, &stations, 11, [22], ConnectionType::Taxi);
-    bidir(graph, &stations, 12, [23], ConnectionType::Taxi);
-    bidir(graph, &stations, 13, [14, 23, 24], ConnectionType::Taxi);
-    bidir(graph, &stations, 14, [15, 25], ConnectionType::Taxi);
-    bidir(graph, &stations, 15, [16, 26, 28], ConnectionType::Taxi);
-    bidir(graph, &stations, 17, [29, 30, 42], ConnectionType::Taxi);
-    bidir(graph, &stations, 18, [31, 43], ConnectionType::Taxi);
-    bidir(graph, &stations, 19, [32], ConnectionType::Taxi);
-    bidir(graph, &stations, 20, [33], ConnectionType::Taxi);
-    bidir(graph, &stations, 21, [33], ConnectionType::Taxi);
-    bidir(graph, &stations, 22, [23, 34, 35], ConnectionType::Taxi);
-    bidir(graph, &stations, 23, [37], ConnectionType::Taxi);
-    bidir(graph, &stations, 24, [37, 38], ConnectionType::Taxi);
-    bidir(graph, &stations, 25, [38, 39], ConnectionType::Taxi);
-    bidir(graph, &stations, 26, [27, 39], ConnectionType::Taxi);
-    bidir(graph, &stations, 27, [28, 40], ConnectionType::Taxi);
-    bidir(graph, &stations, 28, [41], ConnectionType::Taxi);
-    bidir(graph, &stations, 29, [41, 42], ConnectionType::Taxi);
-    bidir(graph, &stations, 30, [42], ConnectionType::Taxi);
-    bidir(graph, &stations, 31, [43, 44], ConnectionType::Taxi);
-    bidir(graph, &stations, 32, [33, 44, 45], ConnectionType::Taxi);
-    bidir(graph, &stations, 33, [46], ConnectionType::Taxi);
-    bidir(graph, &stations, 34, [47, 48], ConnectionType::Taxi);
-    bidir(graph, &stations, 35, [36, 48, 65], ConnectionType::Taxi);
-    bidir(graph, &stations, 36, [37, 49], ConnectionType::Taxi);
-    bidir(graph, &stations, 37, [50], ConnectionType::Taxi);
-    bidir(graph, &stations, 38, [50, 51], ConnectionType::Taxi);
-    bidir(graph, &stations, 39, [51, 52], ConnectionType::Taxi);
-    bidir(graph, &stations, 40, [41, 53], ConnectionType::Taxi);
-    bidir(graph, &stations, 41, [54], ConnectionType::Taxi);
-    bidir(graph, &stations, 42, [56, 72], ConnectionType::Taxi);
-    bidir(graph, &stations, 43, [57], ConnectionType::Taxi);
-    bidir(graph, &stations, 44, [58], ConnectionType::Taxi);
-    bidir(graph, &stations, 45, [46, 58, 59, 60], ConnectionType::Taxi);
-    bidir(graph, &stations, 46, [47, 61], ConnectionType::Taxi);
-    bidir(graph, &stations, 47, [62], ConnectionType::Taxi);
-    bidir(graph, &stations, 48, [62, 63], ConnectionType::Taxi);
-    bidir(graph, &stations, 49, [50, 66], ConnectionType::Taxi);
-    bidir(graph, &stations, 50, [67], ConnectionType::Taxi);
-    bidir(graph, &stations, 51, [52, 67, 68], ConnectionType::Taxi);
-    bidir(graph, &stations, 52, [69], ConnectionType::Taxi);
-    bidir(graph, &stations, 53, [54, 69], ConnectionType::Taxi);
-    bidir(graph, &stations, 54, [55, 70], ConnectionType::Taxi);
-    bidir(graph, &stations, 55, [71], ConnectionType::Taxi);
-    bidir(graph, &stations, 56, [91], ConnectionType::Taxi);
-    bidir(graph, &stations, 57, [58, 73], ConnectionType::Taxi);
-    bidir(graph, &stations, 58, [59, 74, 75], ConnectionType::Taxi);
-    bidir(graph, &stations, 59, [75, 76], ConnectionType::Taxi);
-    bidir(graph, &stations, 60, [61, 76], ConnectionType::Taxi);
-    bidir(graph, &stations, 61, [62, 76, 78], ConnectionType::Taxi);
-    bidir(graph, &stations, 62, [79], ConnectionType::Taxi);
-    bidir(graph, &stations, 63, [64, 79, 80], ConnectionType::Taxi);
-    bidir(graph, &stations, 64, [65, 81], ConnectionType::Taxi);
-    bidir(graph, &stations, 65, [66, 82], ConnectionType::Taxi);
-    bidir(graph, &stations, 66, [67, 82], ConnectionType::Taxi);
-    bidir(graph, &stations, 67, [68, 84], ConnectionType::Taxi);
-    bidir(graph, &stations, 68, [69, 85], ConnectionType::Taxi);
-    bidir(graph, &stations, 69, [86], ConnectionType::Taxi);
-    bidir(graph, &stations, 70, [71, 87], ConnectionType::Taxi);
-    bidir(graph, &stations, 71, [72, 89], ConnectionType::Taxi);
-    bidir(graph, &stations, 72, [90, 91], ConnectionType::Taxi);
-    bidir(graph, &stations, 73, [74, 92], ConnectionType::Taxi);
-    bidir(graph, &stations, 74, [92], ConnectionType::Taxi);
-    bidir(graph, &stations, 75, [94], ConnectionType::Taxi);
-    bidir(graph, &stations, 76, [77], ConnectionType::Taxi);
-    bidir(graph, &stations, 77, [78, 95, 96], ConnectionType::Taxi);
-    bidir(graph, &stations, 78, [79, 97], ConnectionType::Taxi);
-    bidir(graph, &stations, 79, [98], ConnectionType::Taxi);
-    bidir(graph, &stations, 80, [99, 100], ConnectionType::Taxi);
-    bidir(graph, &stations, 81, [82, 100], ConnectionType::Taxi);
-    bidir(graph, &stations, 82, [101], ConnectionType::Taxi);
-    bidir(graph, &stations, 83, [101, 102], ConnectionType::Taxi);
-    bidir(graph, &stations, 84, [85], ConnectionType::Taxi);
-    bidir(graph, &stations, 85, [103], ConnectionType::Taxi);
-    bidir(graph, &stations, 86, [103, 104], ConnectionType::Taxi);
-    bidir(graph, &stations, 87, [88], ConnectionType::Taxi);
-    bidir(graph, &stations, 88, [117], ConnectionType::Taxi);
-    bidir(graph, &stations, 89, [105], ConnectionType::Taxi);
-    bidir(graph, &stations, 90, [91, 105], ConnectionType::Taxi);
-    bidir(graph, &stations, 91, [105, 107], ConnectionType::Taxi);
-    bidir(graph, &stations, 92, [93], ConnectionType::Taxi);
-    bidir(graph, &stations, 93, [94], ConnectionType::Taxi);
-    bidir(graph, &stations, 94, [95], ConnectionType::Taxi);
-    bidir(graph, &stations, 95, [122], ConnectionType::Taxi);
-    bidir(graph, &stations, 96, [97, 109], ConnectionType::Taxi);
-    bidir(graph, &stations, 97, [98, 109], ConnectionType::Taxi);
-    bidir(graph, &stations, 98, [99, 110], ConnectionType::Taxi);
-    bidir(graph, &stations, 99, [110, 112], ConnectionType::Taxi);
-    bidir(graph, &stations, 100, [101, 112, 113], ConnectionType::Taxi);
-    bidir(graph, &stations, 101, [114], ConnectionType::Taxi);
-    bidir(graph, &stations, 102, [103, 115], ConnectionType::Taxi);
-    bidir(graph, &stations, 104, [116], ConnectionType::Taxi);
-    bidir(graph, &stations, 105, [106, 108], ConnectionType::Taxi);
-    bidir(graph, &stations, 106, [107], ConnectionType::Taxi);
-    bidir(graph, &stations, 107, [119], ConnectionType::Taxi);
-    bidir(graph, &stations, 108, [117, 119, 135], ConnectionType::Taxi);
-    bidir(graph, &stations, 109, [110, 124], ConnectionType::Taxi);
-    bidir(graph, &stations, 110, [111], ConnectionType::Taxi);
-    bidir(graph, &stations, 111, [112, 124], ConnectionType::Taxi);
-    bidir(graph, &stations, 112, [125], ConnectionType::Taxi);
-    bidir(graph, &stations, 113, [114, 125], ConnectionType::Taxi);
-    bidir(graph, &stations, 114, [115, 126, 131, 132], ConnectionType::Taxi);
-    bidir(graph, &stations, 115, [126, 127], ConnectionType::Taxi);
-    bidir(graph, &stations, 116, [117, 118, 127], ConnectionType::Taxi);
-    bidir(graph, &stations, 117, [129], ConnectionType::Taxi);
-    bidir(graph, &stations, 118, [129, 134, 142], ConnectionType::Taxi);
-    bidir(graph, &stations, 119, [136], ConnectionType::Taxi);
-    bidir(graph, &stations, 120, [121, 144], ConnectionType::Taxi);
-    bidir(graph, &stations, 121, [122, 145], ConnectionType::Taxi);
-    bidir(graph, &stations, 122, [123, 146], ConnectionType::Taxi);
-    bidir(graph, &stations, 123, [124, 137, 148, 149], ConnectionType::Taxi);
-    bidir(graph, &stations, 124, [130, 138], ConnectionType::Taxi);
-    bidir(graph, &stations, 125, [131], ConnectionType::Taxi);
-    bidir(graph, &stations, 126, [127, 140], ConnectionType::Taxi);
-    bidir(graph, &stations, 127, [133, 134], ConnectionType::Taxi);
-    bidir(graph, &stations, 128, [142, 143, 160, 172, 188],ConnectionType::Taxi);
-    bidir(graph, &stations, 129, [135, 142, 143], ConnectionType::Taxi);
-    bidir(graph, &stations, 130, [131, 139], ConnectionType::Taxi);
-    bidir(graph, &stations, 132, [140], ConnectionType::Taxi);
-    bidir(graph, &stations, 133, [140, 141], ConnectionType::Taxi);
-    bidir(graph, &stations, 134, [141, 142], ConnectionType::Taxi);
-    bidir(graph, &stations, 135, [136, 143, 161], ConnectionType::Taxi);
-    bidir(graph, &stations, 136, [162], ConnectionType::Taxi);
-    bidir(graph, &stations, 137, [147], ConnectionType::Taxi);
-    bidir(graph, &stations, 138, [150, 152], ConnectionType::Taxi);
-    bidir(graph, &stations, 139, [140, 153, 154], ConnectionType::Taxi);
-    bidir(graph, &stations, 140, [154, 156], ConnectionType::Taxi);
-    bidir(graph, &stations, 141, [142, 158], ConnectionType::Taxi);
-    bidir(graph, &stations, 142, [143, 158], ConnectionType::Taxi);
-    bidir(graph, &stations, 143, [160], ConnectionType::Taxi);
-    bidir(graph, &stations, 144, [145, 177], ConnectionType::Taxi);
-    bidir(graph, &stations, 145, [146], ConnectionType::Taxi);
-    bidir(graph, &stations, 146, [163, 147], ConnectionType::Taxi);
-    bidir(graph, &stations, 147, [164], ConnectionType::Taxi);
-    bidir(graph, &stations, 148, [149, 164], ConnectionType::Taxi);
-    bidir(graph, &stations, 149, [150, 165], ConnectionType::Taxi);
-    bidir(graph, &stations, 150, [151], ConnectionType::Taxi);
-    bidir(graph, &stations, 151, [152, 165, 166], ConnectionType::Taxi);
-    bidir(graph, &stations, 152, [153], ConnectionType::Taxi);
-    bidir(graph, &stations, 153, [154, 166, 167], ConnectionType::Taxi);
-    bidir(graph, &stations, 154, [155], ConnectionType::Taxi);
-    bidir(graph, &stations, 155, [156, 167, 168], ConnectionType::Taxi);
-    bidir(graph, &stations, 156, [157, 169], ConnectionType::Taxi);
-    bidir(graph, &stations, 157, [158, 170], ConnectionType::Taxi);
-    bidir(graph, &stations, 158, [159], ConnectionType::Taxi);
-    bidir(graph, &stations, 159, [170, 172, 186, 198], ConnectionType::Taxi);
-    bidir(graph, &stations, 160, [161, 173], ConnectionType::Taxi);
-    bidir(graph, &stations, 161, [174], ConnectionType::Taxi);
-    bidir(graph, &stations, 162, [175], ConnectionType::Taxi);
-    bidir(graph, &stations, 163, [177, 178], ConnectionType::Taxi);
-    bidir(graph, &stations, 164, [178, 179], ConnectionType::Taxi);
-    bidir(graph, &stations, 165, [179, 180], ConnectionType::Taxi);
-    bidir(graph, &stations, 166, [181, 183], ConnectionType::Taxi);
-    bidir(graph, &stations, 167, [168, 183], ConnectionType::Taxi);
-    bidir(graph, &stations, 169, [184], ConnectionType::Taxi);
-    bidir(graph, &stations, 170, [185], ConnectionType::Taxi);
-    bidir(graph, &stations, 171, [173, 175, 199], ConnectionType::Taxi);
-    bidir(graph, &stations, 172, [187], ConnectionType::Taxi);
-    bidir(graph, &stations, 173, [174, 188], ConnectionType::Taxi);
-    bidir(graph, &stations, 174, [175], ConnectionType::Taxi);
-    bidir(graph, &stations, 176, [177, 189], ConnectionType::Taxi);
-    bidir(graph, &stations, 178, [189, 191], ConnectionType::Taxi);
-    bidir(graph, &stations, 179, [191], ConnectionType::Taxi);
-    bidir(graph, &stations, 180, [181, 192, 193], ConnectionType::Taxi);
-    bidir(graph, &stations, 181, [182, 193], ConnectionType::Taxi);
-    bidir(graph, &stations, 182, [195], ConnectionType::Taxi);
-    bidir(graph, &stations, 183, [196], ConnectionType::Taxi);
-    bidir(graph, &stations, 184, [185, 197], ConnectionType::Taxi);
-    bidir(graph, &stations, 185, [186], ConnectionType::Taxi);
-    bidir(graph, &stations, 186, [198], ConnectionType::Taxi);
-    bidir(graph, &stations, 187, [188, 198], ConnectionType::Taxi);
-    bidir(graph, &stations, 188, [199], ConnectionType::Taxi);
-    bidir(graph, &stations, 189, [190], ConnectionType::Taxi);
-    bidir(graph, &stations, 190, [191, 192], ConnectionType::Taxi);
-    bidir(graph, &stations, 191, [192], ConnectionType::Taxi);
-    bidir(graph, &stations, 192, [194], ConnectionType::Taxi);
-    bidir(graph, &stations, 193, [194], ConnectionType::Taxi);
-    bidir(graph, &stations, 194, [195], ConnectionType::Taxi);
-    bidir(graph, &stations, 195, [197], ConnectionType::Taxi);
-    bidir(graph, &stations, 196, [197], ConnectionType::Taxi);
-    bidir(graph, &stations, 198, [199], ConnectionType::Taxi);
+fn connect_taxi<G: StationGraph>(graph: &mut G, stations: &[NodeAddress]) {
+    bidir(graph, stations, 1, [8, 9], ConnectionType::Taxi);
+    bidir(graph, stations, 2, [10, 20], ConnectionType::Taxi);
+    bidir(graph, stations, 3, [4, 11, 12], ConnectionType::Taxi);
+    bidir(graph, stations, 4, [13], ConnectionType::Taxi);
+    bidir(graph, stations, 6, [7, 29], ConnectionType::Taxi);
+    bidir(graph, stations, 7, [17], ConnectionType::Taxi);
+    bidir(graph, stations, 8, [18, 19], ConnectionType::Taxi);
+    bidir(graph, stations, 9, [19, 20], ConnectionType::Taxi);
+    bidir(graph, stations, 10, [11, 21, 34], ConnectionType::Taxi);
+    bidir(graph, stations, 11, [22], ConnectionType::Taxi);
+    bidir(graph, stations, 12, [23], ConnectionType::Taxi);
+    bidir(graph, stations, 13, [14, 23, 24], ConnectionType::Taxi);
+    bidir(graph, stations, 14, [15, 25], ConnectionType::Taxi);
+    bidir(graph, stations, 15, [16, 26, 28], ConnectionType::Taxi);
+    bidir(graph, stations, 17, [29, 30, 42], ConnectionType::Taxi);
+    bidir(graph, stations, 18, [31, 43], ConnectionType::Taxi);
+    bidir(graph, stations, 19, [32], ConnectionType::Taxi);
+    bidir(graph, stations, 20, [33], ConnectionType::Taxi);
+    bidir(graph, stations, 21, [33], ConnectionType::Taxi);
+    bidir(graph, stations, 22, [23, 34, 35], ConnectionType::Taxi);
+    bidir(graph, stations, 23, [37], ConnectionType::Taxi);
+    bidir(graph, stations, 24, [37, 38], ConnectionType::Taxi);
+    bidir(graph, stations, 25, [38, 39], ConnectionType::Taxi);
+    bidir(graph, stations, 26, [27, 39], ConnectionType::Taxi);
+    bidir(graph, stations, 27, [28, 40], ConnectionType::Taxi);
+    bidir(graph, stations, 28, [41], ConnectionType::Taxi);
+    bidir(graph, stations, 29, [41, 42], ConnectionType::Taxi);
+    bidir(graph, stations, 30, [42], ConnectionType::Taxi);
+    bidir(graph, stations, 31, [43, 44], ConnectionType::Taxi);
+    bidir(graph, stations, 32, [33, 44, 45], ConnectionType::Taxi);
+    bidir(graph, stations, 33, [46], ConnectionType::Taxi);
+    bidir(graph, stations, 34, [47, 48], ConnectionType::Taxi);
+    bidir(graph, stations, 35, [36, 48, 65], ConnectionType::Taxi);
+    bidir(graph, stations, 36, [37, 49], ConnectionType::Taxi);
+    bidir(graph, stations, 37, [50], ConnectionType::Taxi);
+    bidir(graph, stations, 38, [50, 51], ConnectionType::Taxi);
+    bidir(graph, stations, 39, [51, 52], ConnectionType::Taxi);
+    bidir(graph, stations, 40, [41, 53], ConnectionType::Taxi);
+    bidir(graph, stations, 41, [54], ConnectionType::Taxi);
+    bidir(graph, stations, 42, [56, 72], ConnectionType::Taxi);
+    bidir(graph, stations, 43, [57], ConnectionType::Taxi);
+    bidir(graph, stations, 44, [58], ConnectionType::Taxi);
+    bidir(graph, stations, 45, [46, 58, 59, 60], ConnectionType::Taxi);
+    bidir(graph, stations, 46, [47, 61], ConnectionType::Taxi);
+    bidir(graph, stations, 47, [62], ConnectionType::Taxi);
+    bidir(graph, stations, 48, [62, 63], ConnectionType::Taxi);
+    bidir(graph, stations, 49, [50, 66], ConnectionType::Taxi);
+    bidir(graph, stations, 50, [67], ConnectionType::Taxi);
+    bidir(graph, stations, 51, [52, 67, 68], ConnectionType::Taxi);
+    bidir(graph, stations, 52, [69], ConnectionType::Taxi);
+    bidir(graph, stations, 53, [54, 69], ConnectionType::Taxi);
+    bidir(graph, stations, 54, [55, 70], ConnectionType::Taxi);
+    bidir(graph, stations, 55, [71], ConnectionType::Taxi);
+    bidir(graph, stations, 56, [91], ConnectionType::Taxi);
+    bidir(graph, stations, 57, [58, 73], ConnectionType::Taxi);
+    bidir(graph, stations, 58, [59, 74, 75], ConnectionType::Taxi);
+    bidir(graph, stations, 59, [75, 76], ConnectionType::Taxi);
+    bidir(graph, stations, 60, [61, 76], ConnectionType::Taxi);
+    bidir(graph, stations, 61, [62, 76, 78], ConnectionType::Taxi);
+    bidir(graph, stations, 62, [79], ConnectionType::Taxi);
+    bidir(graph, stations, 63, [64, 79, 80], ConnectionType::Taxi);
+    bidir(graph, stations, 64, [65, 81], ConnectionType::Taxi);
+    bidir(graph, stations, 65, [66, 82], ConnectionType::Taxi);
+    bidir(graph, stations, 66, [67, 82], ConnectionType::Taxi);
+    bidir(graph, stations, 67, [68, 84], ConnectionType::Taxi);
+    bidir(graph, stations, 68, [69, 85], ConnectionType::Taxi);
+    bidir(graph, stations, 69, [86], ConnectionType::Taxi);
+    bidir(graph, stations, 70, [71, 87], ConnectionType::Taxi);
+    bidir(graph, stations, 71, [72, 89], ConnectionType::Taxi);
+    bidir(graph, stations, 72, [90, 91], ConnectionType::Taxi);
+    bidir(graph, stations, 73, [74, 92], ConnectionType::Taxi);
+    bidir(graph, stations, 74, [92], ConnectionType::Taxi);
+    bidir(graph, stations, 75, [94], ConnectionType::Taxi);
+    bidir(graph, stations, 76, [77], ConnectionType::Taxi);
+    bidir(graph, stations, 77, [78, 95, 96], ConnectionType::Taxi);
+    bidir(graph, stations, 78, [79, 97], ConnectionType::Taxi);
+    bidir(graph, stations, 79, [98], ConnectionType::Taxi);
+    bidir(graph, stations, 80, [99, 100], ConnectionType::Taxi);
+    bidir(graph, stations, 81, [82, 100], ConnectionType::Taxi);
+    bidir(graph, stations, 82, [101], ConnectionType::Taxi);
+    bidir(graph, stations, 83, [101, 102], ConnectionType::Taxi);
+    bidir(graph, stations, 84, [85], ConnectionType::Taxi);
+    bidir(graph, stations, 85, [103], ConnectionType::Taxi);
+    bidir(graph, stations, 86, [103, 104], ConnectionType::Taxi);
+    bidir(graph, stations, 87, [88], ConnectionType::Taxi);
+    bidir(graph, stations, 88, [117], ConnectionType::Taxi);
+    bidir(graph, stations, 89, [105], ConnectionType::Taxi);
+    bidir(graph, stations, 90, [91, 105], ConnectionType::Taxi);
+    bidir(graph, stations, 91, [105, 107], ConnectionType::Taxi);
+    bidir(graph, stations, 92, [93], ConnectionType::Taxi);
+    bidir(graph, stations, 93, [94], ConnectionType::Taxi);
+    bidir(graph, stations, 94, [95], ConnectionType::Taxi);
+    bidir(graph, stations, 95, [122], ConnectionType::Taxi);
+    bidir(graph, stations, 96, [97, 109], ConnectionType::Taxi);
+    bidir(graph, stations, 97, [98, 109], ConnectionType::Taxi);
+    bidir(graph, stations, 98, [99, 110], ConnectionType::Taxi);
+    bidir(graph, stations, 99, [110, 112], ConnectionType::Taxi);
+    bidir(graph, stations, 100, [101, 112, 113], ConnectionType::Taxi);
+    bidir(graph, stations, 101, [114], ConnectionType::Taxi);
+    bidir(graph, stations, 102, [103, 115], ConnectionType::Taxi);
+    bidir(graph, stations, 104, [116], ConnectionType::Taxi);
+    bidir(graph, stations, 105, [106, 108], ConnectionType::Taxi);
+    bidir(graph, stations, 106, [107], ConnectionType::Taxi);
+    bidir(graph, stations, 107, [119], ConnectionType::Taxi);
+    bidir(graph, stations, 108, [117, 119, 135], ConnectionType::Taxi);
+    bidir(graph, stations, 109, [110, 124], ConnectionType::Taxi);
+    bidir(graph, stations, 110, [111], ConnectionType::Taxi);
+    bidir(graph, stations, 111, [112, 124], ConnectionType::Taxi);
+    bidir(graph, stations, 112, [125], ConnectionType::Taxi);
+    bidir(graph, stations, 113, [114, 125], ConnectionType::Taxi);
+    bidir(graph, stations, 114, [115, 126, 131, 132], ConnectionType::Taxi);
+    bidir(graph, stations, 115, [126, 127], ConnectionType::Taxi);
+    bidir(graph, stations, 116, [117, 118, 127], ConnectionType::Taxi);
+    bidir(graph, stations, 117, [129], ConnectionType::Taxi);
+    bidir(graph, stations, 118, [129, 134, 142], ConnectionType::Taxi);
+    bidir(graph, stations, 119, [136], ConnectionType::Taxi);
+    bidir(graph, stations, 120, [121, 144], ConnectionType::Taxi);
+    bidir(graph, stations, 121, [122, 145], ConnectionType::Taxi);
+    bidir(graph, stations, 122, [123, 146], ConnectionType::Taxi);
+    bidir(graph, stations, 123, [124, 137, 148, 149], ConnectionType::Taxi);
+    bidir(graph, stations, 124, [130, 138], ConnectionType::Taxi);
+    bidir(graph, stations, 125, [131], ConnectionType::Taxi);
+    bidir(graph, stations, 126, [127, 140], ConnectionType::Taxi);
+    bidir(graph, stations, 127, [133, 134], ConnectionType::Taxi);
+    bidir(graph, stations, 128, [142, 143, 160, 172, 188],ConnectionType::Taxi);
+    bidir(graph, stations, 129, [135, 142, 143], ConnectionType::Taxi);
+    bidir(graph, stations, 130, [131, 139], ConnectionType::Taxi);
+    bidir(graph, stations, 132, [140], ConnectionType::Taxi);
+    bidir(graph, stations, 133, [140, 141], ConnectionType::Taxi);
+    bidir(graph, stations, 134, [141, 142], ConnectionType::Taxi);
+    bidir(graph, stations, 135, [136, 143, 161], ConnectionType::Taxi);
+    bidir(graph, stations, 136, [162], ConnectionType::Taxi);
+    bidir(graph, stations, 137, [147], ConnectionType::Taxi);
+    bidir(graph, stations, 138, [150, 152], ConnectionType::Taxi);
+    bidir(graph, stations, 139, [140, 153, 154], ConnectionType::Taxi);
+    bidir(graph, stations, 140, [154, 156], ConnectionType::Taxi);
+    bidir(graph, stations, 141, [142, 158], ConnectionType::Taxi);
+    bidir(graph, stations, 142, [143, 158], ConnectionType::Taxi);
+    bidir(graph, stations, 143, [160], ConnectionType::Taxi);
+    bidir(graph, stations, 144, [145, 177], ConnectionType::Taxi);
+    bidir(graph, stations, 145, [146], ConnectionType::Taxi);
+    bidir(graph, stations, 146, [163, 147], ConnectionType::Taxi);
+    bidir(graph, stations, 147, [164], ConnectionType::Taxi);
+    bidir(graph, stations, 148, [149, 164], ConnectionType::Taxi);
+    bidir(graph, stations, 149, [150, 165], ConnectionType::Taxi);
+    bidir(graph, stations, 150, [151], ConnectionType::Taxi);
+    bidir(graph, stations, 151, [152, 165, 166], ConnectionType::Taxi);
+    bidir(graph, stations, 152, [153], ConnectionType::Taxi);
+    bidir(graph, stations, 153, [154, 166, 167], ConnectionType::Taxi);
+    bidir(graph, stations, 154, [155], ConnectionType::Taxi);
+    bidir(graph, stations, 155, [156, 167, 168], ConnectionType::Taxi);
+    bidir(graph, stations, 156, [157, 169], ConnectionType::Taxi);
+    bidir(graph, stations, 157, [158, 170], ConnectionType::Taxi);
+    bidir(graph, stations, 158, [159], ConnectionType::Taxi);
+    bidir(graph, stations, 159, [170, 172, 186, 198], ConnectionType::Taxi);
+    bidir(graph, stations, 160, [161, 173], ConnectionType::Taxi);
+    bidir(graph, stations, 161, [174], ConnectionType::Taxi);
+    bidir(graph, stations, 162, [175], ConnectionType::Taxi);
+    bidir(graph, stations, 163, [177, 178], ConnectionType::Taxi);
+    bidir(graph, stations, 164, [178, 179], ConnectionType::Taxi);
+    bidir(graph, stations, 165, [179, 180], ConnectionType::Taxi);
+    bidir(graph, stations, 166, [181, 183], ConnectionType::Taxi);
+    bidir(graph, stations, 167, [168, 183], ConnectionType::Taxi);
+    bidir(graph, stations, 169, [184], ConnectionType::Taxi);
+    bidir(graph, stations, 170, [185], ConnectionType::Taxi);
+    bidir(graph, stations, 171, [173, 175, 199], ConnectionType::Taxi);
+    bidir(graph, stations, 172, [187], ConnectionType::Taxi);
+    bidir(graph, stations, 173, [174, 188], ConnectionType::Taxi);
+    bidir(graph, stations, 174, [175], ConnectionType::Taxi);
+    bidir(graph, stations, 176, [177, 189], ConnectionType::Taxi);
+    bidir(graph, stations, 178, [189, 191], ConnectionType::Taxi);
+    bidir(graph, stations, 179, [191], ConnectionType::Taxi);
+    bidir(graph, stations, 180, [181, 192, 193], ConnectionType::Taxi);
+    bidir(graph, stations, 181, [182, 193], ConnectionType::Taxi);
+    bidir(graph, stations, 182, [195], ConnectionType::Taxi);
+    bidir(graph, stations, 183, [196], ConnectionType::Taxi);
+    bidir(graph, stations, 184, [185, 197], ConnectionType::Taxi);
+    bidir(graph, stations, 185, [186], ConnectionType::Taxi);
+    bidir(graph, stations, 186, [198], ConnectionType::Taxi);
+    bidir(graph, stations, 187, [188, 198], ConnectionType::Taxi);
+    bidir(graph, stations, 188, [199], ConnectionType::Taxi);
+    bidir(graph, stations, 189, [190], ConnectionType::Taxi);
+    bidir(graph, stations, 190, [191, 192], ConnectionType::Taxi);
+    bidir(graph, stations, 191, [192], ConnectionType::Taxi);
+    bidir(graph, stations, 192, [194], ConnectionType::Taxi);
+    bidir(graph, stations, 193, [194], ConnectionType::Taxi);
+    bidir(graph, stations, 194, [195], ConnectionType::Taxi);
+    bidir(graph, stations, 195, [197], ConnectionType::Taxi);
+    bidir(graph, stations, 196, [197], ConnectionType::Taxi);
+    bidir(graph, stations, 198, [199], ConnectionType::Taxi);
 }
 
 #[rustfmt::skip]
-fn connect_bus(graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>, stations: &[NodeAddress]) {
-    bidir(graph, &stations, 1, [46, 58], ConnectionType::Bus);
-    bidir(graph, &stations, 3, [22, 23], ConnectionType::Bus);
-    bidir(graph, &stations, 7, [42], ConnectionType::Bus);
-    bidir(graph, &stations, 13, [14, 23, 52], ConnectionType::Bus);
-    bidir(graph, &stations, 14, [15], ConnectionType::Bus);
-    bidir(graph, &stations, 15, [29, 41], ConnectionType::Bus);
-    bidir(graph, &stations, 22, [23, 34, 56], ConnectionType::Bus);
-    bidir(graph, &stations, 23, [67], ConnectionType::Bus);
-    bidir(graph, &stations, 29, [41, 42, 55], ConnectionType::Bus);
-    bidir(graph, &stations, 34, [46, 63], ConnectionType::Bus);
-    bidir(graph, &stations, 41, [52, 87], ConnectionType::Bus);
-    bidir(graph, &stations, 42, [72], ConnectionType::Bus);
-    bidir(graph, &stations, 46, [58, 78], ConnectionType::Bus);
-    bidir(graph, &stations, 52, [67, 86], ConnectionType::Bus);
-    bidir(graph, &stations, 55, [89], ConnectionType::Bus);
-    bidir(graph, &stations, 63, [65, 79, 100], ConnectionType::Bus);
-    bidir(graph, &stations, 65, [67, 82], ConnectionType::Bus);
-    bidir(graph, &stations, 67, [82, 102], ConnectionType::Bus);
-    bidir(graph, &stations, 72, [105, 107], ConnectionType::Bus);
-    bidir(graph, &stations, 77, [78, 124], ConnectionType::Bus);
-    bidir(graph, &stations, 78, [79], ConnectionType::Bus);
-    bidir(graph, &stations, 82, [100, 140], ConnectionType::Bus);
-    bidir(graph, &stations, 86, [87, 102, 116], ConnectionType::Bus);
-    bidir(graph, &stations, 87, [105], ConnectionType::Bus);
-    bidir(graph, &stations, 89, [105], ConnectionType::Bus);
-    bidir(graph, &stations, 100, [111], ConnectionType::Bus);
-    bidir(graph, &stations, 102, [127], ConnectionType::Bus);
-    bidir(graph, &stations, 105, [107, 108], ConnectionType::Bus);
-    bidir(graph, &stations, 107, [161], ConnectionType::Bus);
-    bidir(graph, &stations, 108, [116, 135], ConnectionType::Bus);
-    bidir(graph, &stations, 116, [127, 142], ConnectionType::Bus);
-    bidir(graph, &stations, 122, [123, 144], ConnectionType::Bus);
-    bidir(graph, &stations, 123, [124, 165, 144], ConnectionType::Bus);
-    bidir(graph, &stations, 124, [153], ConnectionType::Bus);
-    bidir(graph, &stations, 127, [133], ConnectionType::Bus);
-    bidir(graph, &stations, 128, [135, 142, 161, 187, 199], ConnectionType::Bus);
-    bidir(graph, &stations, 133, [140], ConnectionType::Bus);
-    bidir(graph, &stations, 142, [157], ConnectionType::Bus);
-    bidir(graph, &stations, 144, [163], ConnectionType::Bus);
-    bidir(graph, &stations, 153, [154, 180, 184], ConnectionType::Bus);
-    bidir(graph, &stations, 154, [156], ConnectionType::Bus);
-    bidir(graph, &stations, 156, [157, 184], ConnectionType::Bus);
-    bidir(graph, &stations, 157, [185], ConnectionType::Bus);
-    bidir(graph, &stations, 161, [199], ConnectionType::Bus);
-    bidir(graph, &stations, 163, [176, 191], ConnectionType::Bus);
-    bidir(graph, &stations, 165, [180, 191], ConnectionType::Bus);
-    bidir(graph, &stations, 176, [190], ConnectionType::Bus);
-    bidir(graph, &stations, 180, [184, 190], ConnectionType::Bus);
-    bidir(graph, &stations, 184, [185], ConnectionType::Bus);
-    bidir(graph, &stations, 185, [187], ConnectionType::Bus);
-    bidir(graph, &stations, 190, [191], ConnectionType::Bus);
+fn connect_bus<G: StationGraph>(graph: &mut G, stations: &[NodeAddress]) {
+    bidir(graph, stations, 1, [46, 58], ConnectionType::Bus);
+    bidir(graph, stations, 3, [22, 23], ConnectionType::Bus);
+    bidir(graph, stations, 7, [42], ConnectionType::Bus);
+    bidir(graph, stations, 13, [14, 23, 52], ConnectionType::Bus);
+    bidir(graph, stations, 14, [15], ConnectionType::Bus);
+    bidir(graph, stations, 15, [29, 41], ConnectionType::Bus);
+    bidir(graph, stations, 22, [23, 34, 56], ConnectionType::Bus);
+    bidir(graph, stations, 23, [67], ConnectionType::Bus);
+    bidir(graph, stations, 29, [41, 42, 55], ConnectionType::Bus);
+    bidir(graph, stations, 34, [46, 63], ConnectionType::Bus);
+    bidir(graph, stations, 41, [52, 87], ConnectionType::Bus);
+    bidir(graph, stations, 42, [72], ConnectionType::Bus);
+    bidir(graph, stations, 46, [58, 78], ConnectionType::Bus);
+    bidir(graph, stations, 52, [67, 86], ConnectionType::Bus);
+    bidir(graph, stations, 55, [89], ConnectionType::Bus);
+    bidir(graph, stations, 63, [65, 79, 100], ConnectionType::Bus);
+    bidir(graph, stations, 65, [67, 82], ConnectionType::Bus);
+    bidir(graph, stations, 67, [82, 102], ConnectionType::Bus);
+    bidir(graph, stations, 72, [105, 107], ConnectionType::Bus);
+    bidir(graph, stations, 77, [78, 124], ConnectionType::Bus);
+    bidir(graph, stations, 78, [79], ConnectionType::Bus);
+    bidir(graph, stations, 82, [100, 140], ConnectionType::Bus);
+    bidir(graph, stations, 86, [87, 102, 116], ConnectionType::Bus);
+    bidir(graph, stations, 87, [105], ConnectionType::Bus);
+    bidir(graph, stations, 89, [105], ConnectionType::Bus);
+    bidir(graph, stations, 100, [111], ConnectionType::Bus);
+    bidir(graph, stations, 102, [127], ConnectionType::Bus);
+    bidir(graph, stations, 105, [107, 108], ConnectionType::Bus);
+    bidir(graph, stations, 107, [161], ConnectionType::Bus);
+    bidir(graph, stations, 108, [116, 135], ConnectionType::Bus);
+    bidir(graph, stations, 116, [127, 142], ConnectionType::Bus);
+    bidir(graph, stations, 122, [123, 144], ConnectionType::Bus);
+    bidir(graph, stations, 123, [124, 165, 144], ConnectionType::Bus);
+    bidir(graph, stations, 124, [153], ConnectionType::Bus);
+    bidir(graph, stations, 127, [133], ConnectionType::Bus);
+    bidir(graph, stations, 128, [135, 142, 161, 187, 199], ConnectionType::Bus);
+    bidir(graph, stations, 133, [140], ConnectionType::Bus);
+    bidir(graph, stations, 142, [157], ConnectionType::Bus);
+    bidir(graph, stations, 144, [163], ConnectionType::Bus);
+    bidir(graph, stations, 153, [154, 180, 184], ConnectionType::Bus);
+    bidir(graph, stations, 154, [156], ConnectionType::Bus);
+    bidir(graph, stations, 156, [157, 184], ConnectionType::Bus);
+    bidir(graph, stations, 157, [185], ConnectionType::Bus);
+    bidir(graph, stations, 161, [199], ConnectionType::Bus);
+    bidir(graph, stations, 163, [176, 191], ConnectionType::Bus);
+    bidir(graph, stations, 165, [180, 191], ConnectionType::Bus);
+    bidir(graph, stations, 176, [190], ConnectionType::Bus);
+    bidir(graph, stations, 180, [184, 190], ConnectionType::Bus);
+    bidir(graph, stations, 184, [185], ConnectionType::Bus);
+    bidir(graph, stations, 185, [187], ConnectionType::Bus);
+    bidir(graph, stations, 190, [191], ConnectionType::Bus);
 }
 
 #[rustfmt::skip]
-fn connect_subways(graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>, stations: &[NodeAddress]) {
-    bidir(graph, &stations, 1, [46], ConnectionType::Underground);
-    bidir(graph, &stations, 13, [46, 67, 89], ConnectionType::Underground);
-    bidir(graph, &stations, 46, [74, 79], ConnectionType::Underground);
-    bidir(graph, &stations, 67, [79, 89, 111], ConnectionType::Underground);
-    bidir(graph, &stations, 79, [93, 111], ConnectionType::Underground);
-    bidir(graph, &stations, 89, [128, 140], ConnectionType::Underground);
-    bidir(graph, &stations, 111, [153, 163], ConnectionType::Underground);
-    bidir(graph, &stations, 128, [140, 185], ConnectionType::Underground);
-    bidir(graph, &stations, 140, [153], ConnectionType::Underground);
-    bidir(graph, &stations, 153, [185], ConnectionType::Underground);
+fn connect_subways<G: StationGraph>(graph: &mut G, stations: &[NodeAddress]) {
+    bidir(graph, stations, 1, [46], ConnectionType::Underground);
+    bidir(graph, stations, 13, [46, 67, 89], ConnectionType::Underground);
+    bidir(graph, stations, 46, [74, 79], ConnectionType::Underground);
+    bidir(graph, stations, 67, [79, 89, 111], ConnectionType::Underground);
+    bidir(graph, stations, 79, [93, 111], ConnectionType::Underground);
+    bidir(graph, stations, 89, [128, 140], ConnectionType::Underground);
+    bidir(graph, stations, 111, [153, 163], ConnectionType::Underground);
+    bidir(graph, stations, 128, [140, 185], ConnectionType::Underground);
+    bidir(graph, stations, 140, [153], ConnectionType::Underground);
+    bidir(graph, stations, 153, [185], ConnectionType::Underground);
 }
 
 #[rustfmt::skip]
-fn connect_ferries(graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>, stations: &[NodeAddress]) {
-    bidir(graph, &stations, 108, [115], ConnectionType::Ferry);
-    bidir(graph, &stations, 115, [157], ConnectionType::Ferry);
-    bidir(graph, &stations, 157, [194], ConnectionType::Ferry);
+fn connect_ferries<G: StationGraph>(graph: &mut G, stations: &[NodeAddress]) {
+    bidir(graph, stations, 108, [115], ConnectionType::Ferry);
+    bidir(graph, stations, 115, [157], ConnectionType::Ferry);
+    bidir(graph, stations, 157, [194], ConnectionType::Ferry);
 }
 
 /// Bidirectionally link nodes.
-fn bidir<L: IntoIterator<Item = usize>>(
-    graph: &mut EmbeddedPropertyGraph<Station, ConnectionType>,
+fn bidir<G: StationGraph, L: IntoIterator<Item = usize>>(
+    graph: &mut G,
     stations: &[NodeAddress],
     from: usize,
     to: L,
@@ -593,6 +646,8 @@ fn bidir<L: IntoIterator<Item = usize>>(
     // graph.link_to(&stations[from], &stations[to], connection);
     // graph.link_to(&stations[to], &stations[from], connection);
     for to in to {
-        graph.link_bidir(&stations[to - 1], &stations[from], connection);
+        graph
+            .link_bidir(&stations[to - 1], &stations[from], connection)
+            .expect("the stations exist in the graph");
     }
 }
@@ -3,14 +3,17 @@
 #![allow(dead_code)]
 
 use crate::embedded_property_graph::EmbeddedPropertyGraph;
+use crate::node_address::NodeAddress;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NodeType {
     Movie(Movie),
     Person(Person),
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RelationType {
     /// Person acted in movie
     ActedIn(ActedIn),
@@ -27,12 +30,14 @@ pub enum RelationType {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Person {
     name: String,
     born: u16,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Movie {
     title: String,
     released: u16,
@@ -40,16 +45,25 @@ pub struct Movie {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ActedIn {
     roles: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Review {
     summary: String,
     rating: u8,
 }
 
+impl Review {
+    /// The rating of the movie, between 0 and 100.
+    pub fn rating(&self) -> u8 {
+        self.rating
+    }
+}
+
 pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
     let mut graph = EmbeddedPropertyGraph::default();
 
@@ -93,44 +107,49 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         born: 1978,
     }));
 
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &matrix,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Neo".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &carrie,
         &matrix,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Trinity".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &laurence,
         &matrix,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Morpheus".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &hugo,
         &matrix,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Agent Smith".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &emil_e,
         &matrix,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Emil".into()]),
         }),
     );
-    graph.link_to(&lilly_w, &matrix, RelationType::Directed);
-    graph.link_to(&lana_w, &matrix, RelationType::Directed);
-    graph.link_to(&joel_s, &matrix, RelationType::Produced);
+    link(&mut graph, &lilly_w, &matrix, RelationType::Directed);
+    link(&mut graph, &lana_w, &matrix, RelationType::Directed);
+    link(&mut graph, &joel_s, &matrix, RelationType::Produced);
 
     // The Matrix Reloaded
     let matrix_reloaded = graph.add(NodeType::Movie(Movie {
@@ -138,37 +157,56 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         released: 2003,
         tagline: "Free your mind".into(),
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &matrix_reloaded,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Neo".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &carrie,
         &matrix_reloaded,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Trinity".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &laurence,
         &matrix_reloaded,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Morpheus".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &hugo,
         &matrix_reloaded,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Agent Smith".into()]),
         }),
     );
-    graph.link_to(&lilly_w, &matrix_reloaded, RelationType::Directed);
-    graph.link_to(&lana_w, &matrix_reloaded, RelationType::Directed);
-    graph.link_to(&joel_s, &matrix_reloaded, RelationType::Produced);
+    link(
+        &mut graph,
+        &lilly_w,
+        &matrix_reloaded,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &lana_w,
+        &matrix_reloaded,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &joel_s,
+        &matrix_reloaded,
+        RelationType::Produced,
+    );
 
     // The Matrix Revolutions
     let matrix_revolutions = graph.add(NodeType::Movie(Movie {
@@ -176,37 +214,56 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         released: 2003,
         tagline: "Everything that has a beginning has an end".into(),
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &matrix_revolutions,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Neo".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &carrie,
         &matrix_revolutions,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Trinity".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &laurence,
         &matrix_revolutions,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Morpheus".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &hugo,
         &matrix_revolutions,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Agent Smith".into()]),
         }),
     );
-    graph.link_to(&lilly_w, &matrix_revolutions, RelationType::Directed);
-    graph.link_to(&lana_w, &matrix_revolutions, RelationType::Directed);
-    graph.link_to(&joel_s, &matrix_revolutions, RelationType::Produced);
+    link(
+        &mut graph,
+        &lilly_w,
+        &matrix_revolutions,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &lana_w,
+        &matrix_revolutions,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &joel_s,
+        &matrix_revolutions,
+        RelationType::Produced,
+    );
 
     // The Devil's Advocate
     let devils_advocate = graph.add(NodeType::Movie(Movie {
@@ -226,28 +283,36 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Taylor Hackford".into(),
         born: 1944,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &devils_advocate,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Kevin Lomax".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &charlize,
         &devils_advocate,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Mary Ann Lomax".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &al,
         &devils_advocate,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["John Milton".into()]),
         }),
     );
-    graph.link_to(&taylor, &devils_advocate, RelationType::Directed);
+    link(
+        &mut graph,
+        &taylor,
+        &devils_advocate,
+        RelationType::Directed,
+    );
 
     // A Few Good Men
     let a_few_good_men = graph.add(NodeType::Movie(Movie {
@@ -307,92 +372,104 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Aaron Sorkin".into(),
         born: 1961,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_c,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Lt. Daniel Kaffee".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jack_n,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Col. Nathan R. Jessup".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &demi_m,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Lt. Cdr. JoAnne Galloway".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kevin_b,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Capt. Jack Ross".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kiefer_s,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Lt. Jonathan Kendrick".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &noah_w,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Cpl. Jeffrey Barnes".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &cuba_g,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Cpl. Carl Hammaker".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kevin_p,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Lt. Sam Weinberg".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jtw,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Lt. Col. Matthew Andrew Markinson".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &james_m,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Pfc. Louden Downey".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &christopher_g,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dr. Stone".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &aaron_s,
         &a_few_good_men,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Man in Bar".into()]),
         }),
     );
-    graph.link_to(&rob_r, &matrix, RelationType::Directed);
-    graph.link_to(&aaron_s, &matrix, RelationType::Wrote);
+    link(&mut graph, &rob_r, &a_few_good_men, RelationType::Directed);
+    link(&mut graph, &aaron_s, &a_few_good_men, RelationType::Wrote);
 
     // Top Gun
     let top_gun = graph.add(NodeType::Movie(Movie {
@@ -428,50 +505,56 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Jim Cash".into(),
         born: 1941,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_c,
         &top_gun,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Maverick".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kelly_m,
         &top_gun,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Charlie".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &val_k,
         &top_gun,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Iceman".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &anthony_e,
         &top_gun,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Goose".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_s,
         &top_gun,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Viper".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &meg_r,
         &top_gun,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Carole".into()]),
         }),
     );
-    graph.link_to(&tony_s, &top_gun, RelationType::Directed);
-    graph.link_to(&jim_c, &top_gun, RelationType::Wrote);
+    link(&mut graph, &tony_s, &top_gun, RelationType::Directed);
+    link(&mut graph, &jim_c, &top_gun, RelationType::Wrote);
 
     let jerry_maguire = graph.add(NodeType::Movie(Movie {
         title: "Jerry Maguire".into(),
@@ -510,72 +593,91 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Cameron Crowe".into(),
         born: 1957,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_c,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jerry Maguire".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &cuba_g,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Rod Tidwell".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &renee_z,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dorothy Boyd".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kelly_p,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Avery Bishop".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jerry_o,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Frank Cushman".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jay_m,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Bob Sugar".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &bonnie_h,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Laurel Boyd".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &regina_k,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Marcee Tidwell".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jonathan_l,
         &jerry_maguire,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Ray Boyd".into()]),
         }),
     );
-    graph.link_to(&cameron_c, &jerry_maguire, RelationType::Directed);
-    graph.link_to(&cameron_c, &jerry_maguire, RelationType::Produced);
-    graph.link_to(&cameron_c, &jerry_maguire, RelationType::Wrote);
+    link(
+        &mut graph,
+        &cameron_c,
+        &jerry_maguire,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &cameron_c,
+        &jerry_maguire,
+        RelationType::Produced,
+    );
+    link(&mut graph, &cameron_c, &jerry_maguire, RelationType::Wrote);
 
     let stand_by_me = graph.add(NodeType::Movie(Movie { title: "Stand By Me".into(), released: 1986, tagline: "For some, it's the last real taste of innocence, and the first real taste of life. But for everyone, it's the time that memories are made of.".into()}));
     let river_p = graph.add(NodeType::Person(Person {
@@ -598,56 +700,63 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Marshall Bell".into(),
         born: 1942,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &wil_w,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Gordie Lachance".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &river_p,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Chris Chambers".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jerry_o,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Vern Tessio".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &corey_f,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Teddy Duchamp".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &john_c,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Denny Lachance".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kiefer_s,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Ace Merrill".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &marshall_b,
         &stand_by_me,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Mr. Lachance".into()]),
         }),
     );
-    graph.link_to(&rob_r, &stand_by_me, RelationType::Directed);
+    link(&mut graph, &rob_r, &stand_by_me, RelationType::Directed);
 
     let as_good_as_it_gets = graph.add(NodeType::Movie(Movie {
         title: "As Good as It Gets".into(),
@@ -666,35 +775,44 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "James L. Brooks".into(),
         born: 1940,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &jack_n,
         &as_good_as_it_gets,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Melvin Udall".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &helen_h,
         &as_good_as_it_gets,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Carol Connelly".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &greg_k,
         &as_good_as_it_gets,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Simon Bishop".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &cuba_g,
         &as_good_as_it_gets,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Frank Sachs".into()]),
         }),
     );
-    graph.link_to(&james_b, &as_good_as_it_gets, RelationType::Directed);
+    link(
+        &mut graph,
+        &james_b,
+        &as_good_as_it_gets,
+        RelationType::Directed,
+    );
 
     let what_dreams_may_come = graph.add(NodeType::Movie(Movie {
         title: "What Dreams May Come".into(),
@@ -721,42 +839,52 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Vincent Ward".into(),
         born: 1956,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &robin,
         &what_dreams_may_come,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Chris Nielsen".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &cuba_g,
         &what_dreams_may_come,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Albert Lewis".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &annabella_s,
         &what_dreams_may_come,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Annie Collins-Nielsen".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &max_s,
         &what_dreams_may_come,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["The Tracker".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &werner_h,
         &what_dreams_may_come,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["The Face".into()]),
         }),
     );
-    graph.link_to(&vincent_w, &what_dreams_may_come, RelationType::Directed);
+    link(
+        &mut graph,
+        &vincent_w,
+        &what_dreams_may_come,
+        RelationType::Directed,
+    );
 
     let snow_fallingon_cedars = graph.add(NodeType::Movie(Movie {
         title: "Snow Falling on Cedars".into(),
@@ -779,35 +907,44 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Scott Hicks".into(),
         born: 1953,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &ethan_h,
         &snow_fallingon_cedars,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Ishmael Chambers".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &rick_y,
         &snow_fallingon_cedars,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Kazuo Miyamoto".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &max_s,
         &snow_fallingon_cedars,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Nels Gudmundsson".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &james_c,
         &snow_fallingon_cedars,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Judge Fielding".into()]),
         }),
     );
-    graph.link_to(&scott_h, &snow_fallingon_cedars, RelationType::Directed);
+    link(
+        &mut graph,
+        &scott_h,
+        &snow_fallingon_cedars,
+        RelationType::Directed,
+    );
 
     let youve_got_mail = graph.add(NodeType::Movie(Movie {
         title: "You've Got Mail".into(),
@@ -834,49 +971,55 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Nora Ephron".into(),
         born: 1941,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &youve_got_mail,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Joe Fox".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &meg_r,
         &youve_got_mail,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Kathleen Kelly".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &greg_k,
         &youve_got_mail,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Frank Navasky".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &parker_p,
         &youve_got_mail,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Patricia Eden".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &dave_c,
         &youve_got_mail,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Kevin Jackson".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &steve_z,
         &youve_got_mail,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["George Pappas".into()]),
         }),
     );
-    graph.link_to(&nora_e, &youve_got_mail, RelationType::Directed);
+    link(&mut graph, &nora_e, &youve_got_mail, RelationType::Directed);
 
     let sleepless_in_seattle = graph.add(NodeType::Movie(Movie { title: "Sleepless in Seattle".into(), released: 1993, tagline: "What if someone you never met, someone you never saw, someone you never knew was the only someone for you?".into()}));
     let rita_w = graph.add(NodeType::Person(Person {
@@ -895,49 +1038,60 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Rosie O'Donnell".into(),
         born: 1962,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &sleepless_in_seattle,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Sam Baldwin".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &meg_r,
         &sleepless_in_seattle,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Annie Reed".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &rita_w,
         &sleepless_in_seattle,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Suzy".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &bill_pull,
         &sleepless_in_seattle,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Walter".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &victor_g,
         &sleepless_in_seattle,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Greg".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &rosie_o,
         &sleepless_in_seattle,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Becky".into()]),
         }),
     );
-    graph.link_to(&nora_e, &sleepless_in_seattle, RelationType::Directed);
+    link(
+        &mut graph,
+        &nora_e,
+        &sleepless_in_seattle,
+        RelationType::Directed,
+    );
 
     let joe_versusthe_volcano = graph.add(NodeType::Movie(Movie {
         title: "Joe Versus the Volcano".into(),
@@ -952,28 +1106,36 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Nathan Lane".into(),
         born: 1956,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &joe_versusthe_volcano,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Joe Banks".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &meg_r,
         &joe_versusthe_volcano,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["DeDe', 'Angelica Graynamore', 'Patricia Graynamore".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &nathan,
         &joe_versusthe_volcano,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Baw".into()]),
         }),
     );
-    graph.link_to(&john_s, &joe_versusthe_volcano, RelationType::Directed);
+    link(
+        &mut graph,
+        &john_s,
+        &joe_versusthe_volcano,
+        RelationType::Directed,
+    );
 
     let when_harry_met_sally = graph.add(NodeType::Movie(Movie {
         title: "When Harry Met Sally".into(),
@@ -992,38 +1154,62 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Bruno Kirby".into(),
         born: 1949,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &billy_c,
         &when_harry_met_sally,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Harry Burns".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &meg_r,
         &when_harry_met_sally,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Sally Albright".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &carrie_f,
         &when_harry_met_sally,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Marie".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &bruno_k,
         &when_harry_met_sally,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jess".into()]),
         }),
     );
-    graph.link_to(&rob_r, &when_harry_met_sally, RelationType::Directed);
-    graph.link_to(&rob_r, &when_harry_met_sally, RelationType::Produced);
-    graph.link_to(&nora_e, &when_harry_met_sally, RelationType::Produced);
-    graph.link_to(&nora_e, &when_harry_met_sally, RelationType::Wrote);
+    link(
+        &mut graph,
+        &rob_r,
+        &when_harry_met_sally,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &rob_r,
+        &when_harry_met_sally,
+        RelationType::Produced,
+    );
+    link(
+        &mut graph,
+        &nora_e,
+        &when_harry_met_sally,
+        RelationType::Produced,
+    );
+    link(
+        &mut graph,
+        &nora_e,
+        &when_harry_met_sally,
+        RelationType::Wrote,
+    );
 
     let that_thing_you_do = graph.add(NodeType::Movie(Movie {
         title: "That Thing You Do".into(),
@@ -1036,28 +1222,36 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Liv Tyler".into(),
         born: 1977,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &that_thing_you_do,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Mr. White".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &liv_t,
         &that_thing_you_do,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Faye Dolan".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &charlize,
         &that_thing_you_do,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Tina".into()]),
         }),
     );
-    graph.link_to(&tom_h, &that_thing_you_do, RelationType::Directed);
+    link(
+        &mut graph,
+        &tom_h,
+        &that_thing_you_do,
+        RelationType::Directed,
+    );
 
     let the_replacements = graph.add(NodeType::Movie(Movie {
         title: "The Replacements".into(),
@@ -1080,35 +1274,44 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Howard Deutch".into(),
         born: 1950,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &the_replacements,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Shane Falco".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &brooke,
         &the_replacements,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Annabelle Farrell".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &gene,
         &the_replacements,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jimmy McGinty".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &orlando,
         &the_replacements,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Clifford Franklin".into()]),
         }),
     );
-    graph.link_to(&howard, &the_replacements, RelationType::Directed);
+    link(
+        &mut graph,
+        &howard,
+        &the_replacements,
+        RelationType::Directed,
+    );
 
     let rescue_dawn = graph.add(NodeType::Movie(Movie {
         title: "RescueDawn".into(),
@@ -1123,35 +1326,39 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Zach Grenier".into(),
         born: 1954,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &marshall_b,
         &rescue_dawn,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Admiral".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &christian_b,
         &rescue_dawn,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dieter Dengler".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &zach_g,
         &rescue_dawn,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Squad Leader".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &steve_z,
         &rescue_dawn,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Duane".into()]),
         }),
     );
-    graph.link_to(&werner_h, &rescue_dawn, RelationType::Directed);
+    link(&mut graph, &werner_h, &rescue_dawn, RelationType::Directed);
 
     let the_birdcage = graph.add(NodeType::Movie(Movie {
         title: "The Birdcage".into(),
@@ -1162,28 +1369,31 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Mike Nichols".into(),
         born: 1931,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &robin,
         &the_birdcage,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Armand Goldman".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &nathan,
         &the_birdcage,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Albert Goldman".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &gene,
         &the_birdcage,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Sen. Kevin Keeley".into()]),
         }),
     );
-    graph.link_to(&mike_n, &the_birdcage, RelationType::Directed);
+    link(&mut graph, &mike_n, &the_birdcage, RelationType::Directed);
 
     let unforgiven = graph.add(NodeType::Movie(Movie {
         title: "unforgiven".into(),
@@ -1198,28 +1408,31 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Clint Eastwood".into(),
         born: 1930,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &richard_h,
         &unforgiven,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["English Bob".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &clint_e,
         &unforgiven,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Bill Munny".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &gene,
         &unforgiven,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Little Bill Daggett".into()]),
         }),
     );
-    graph.link_to(&clint_e, &unforgiven, RelationType::Directed);
+    link(&mut graph, &clint_e, &unforgiven, RelationType::Directed);
 
     let johnny_mnemonic = graph.add(NodeType::Movie(Movie {
         title: "Johnny Mnemonic".into(),
@@ -1242,35 +1455,44 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Robert Longo".into(),
         born: 1953,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &johnny_mnemonic,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Johnny Mnemonic".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &takeshi,
         &johnny_mnemonic,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Takahashi".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &dina,
         &johnny_mnemonic,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jane".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &ice_t,
         &johnny_mnemonic,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["J-Bone".into()]),
         }),
     );
-    graph.link_to(&robert_l, &johnny_mnemonic, RelationType::Directed);
+    link(
+        &mut graph,
+        &robert_l,
+        &johnny_mnemonic,
+        RelationType::Directed,
+    );
 
     let cloud_atlas = graph.add(NodeType::Movie(Movie {
         title: "Cloud Atlas".into(),
@@ -1297,7 +1519,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Stefan Arndt".into(),
         born: 1961,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &cloud_atlas,
         RelationType::ActedIn(ActedIn {
@@ -1306,26 +1529,38 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             ]),
         }),
     );
-    graph.link_to(&hugo, &cloud_atlas, RelationType::ActedIn(ActedIn { roles: Vec::from_iter(["Bill Smoke', 'Haskell Moore', 'Tadeusz Kesselring', 'Nurse Noakes', 'Boardman Mephi', 'Old Georgie".into()]) }) );
-    graph.link_to(
+    link(&mut graph, &hugo, &cloud_atlas, RelationType::ActedIn(ActedIn { roles: Vec::from_iter(["Bill Smoke', 'Haskell Moore', 'Tadeusz Kesselring', 'Nurse Noakes', 'Boardman Mephi', 'Old Georgie".into()]) }) );
+    link(
+        &mut graph,
         &halle_b,
         &cloud_atlas,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Luisa Rey', 'Jocasta Ayrs', 'Ovid', 'Meronym".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jim_b,
         &cloud_atlas,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Vyvyan Ayrs', 'Captain Molyneux', 'Timothy Cavendish".into()]),
         }),
     );
-    graph.link_to(&tom_t, &cloud_atlas, RelationType::Directed);
-    graph.link_to(&lilly_w, &cloud_atlas, RelationType::Directed);
-    graph.link_to(&lana_w, &cloud_atlas, RelationType::Directed);
-    graph.link_to(&david_mitchell, &cloud_atlas, RelationType::Wrote);
-    graph.link_to(&stefan_arndt, &cloud_atlas, RelationType::Produced);
+    link(&mut graph, &tom_t, &cloud_atlas, RelationType::Directed);
+    link(&mut graph, &lilly_w, &cloud_atlas, RelationType::Directed);
+    link(&mut graph, &lana_w, &cloud_atlas, RelationType::Directed);
+    link(
+        &mut graph,
+        &david_mitchell,
+        &cloud_atlas,
+        RelationType::Wrote,
+    );
+    link(
+        &mut graph,
+        &stefan_arndt,
+        &cloud_atlas,
+        RelationType::Produced,
+    );
 
     let the_da_vinci_code = graph.add(NodeType::Movie(Movie {
         title: "The Da Vinci Code".into(),
@@ -1348,35 +1583,44 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Ron Howard".into(),
         born: 1954,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &the_da_vinci_code,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dr. Robert Langdon".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &ian_m,
         &the_da_vinci_code,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Sir Leight Teabing".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &audrey_t,
         &the_da_vinci_code,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Sophie Neveu".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &paul_b,
         &the_da_vinci_code,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Silas".into()]),
         }),
     );
-    graph.link_to(&ron_h, &the_da_vinci_code, RelationType::Directed);
+    link(
+        &mut graph,
+        &ron_h,
+        &the_da_vinci_code,
+        RelationType::Directed,
+    );
 
     let vfor_vendetta = graph.add(NodeType::Movie(Movie {
         title: "V for Vendetta".into(),
@@ -1399,47 +1643,52 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "John Hurt".into(),
         born: 1940,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &hugo,
         &vfor_vendetta,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["V".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &natalie_p,
         &vfor_vendetta,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Evey Hammond".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &stephen_r,
         &vfor_vendetta,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Eric Finch".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &john_h,
         &vfor_vendetta,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["High Chancellor Adam Sutler".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &ben_m,
         &vfor_vendetta,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dascomb".into()]),
         }),
     );
-    graph.link_to(&james_m, &vfor_vendetta, RelationType::Directed);
-    graph.link_to(&lilly_w, &vfor_vendetta, RelationType::Produced);
-    graph.link_to(&lana_w, &vfor_vendetta, RelationType::Produced);
-    graph.link_to(&joel_s, &vfor_vendetta, RelationType::Produced);
-    graph.link_to(&lilly_w, &vfor_vendetta, RelationType::Wrote);
-    graph.link_to(&lana_w, &vfor_vendetta, RelationType::Wrote);
+    link(&mut graph, &james_m, &vfor_vendetta, RelationType::Directed);
+    link(&mut graph, &lilly_w, &vfor_vendetta, RelationType::Produced);
+    link(&mut graph, &lana_w, &vfor_vendetta, RelationType::Produced);
+    link(&mut graph, &joel_s, &vfor_vendetta, RelationType::Produced);
+    link(&mut graph, &lilly_w, &vfor_vendetta, RelationType::Wrote);
+    link(&mut graph, &lana_w, &vfor_vendetta, RelationType::Wrote);
 
     let speed_racer = graph.add(NodeType::Movie(Movie {
         title: "Speed Racer".into(),
@@ -1470,60 +1719,67 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Rain".into(),
         born: 1982,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &emile_h,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Speed Racer".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &john_g,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Pops".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &susan_s,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Mom".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &matthew_f,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Racer X".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &christina_r,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Trixie".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &rain,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Taejo Togokahn".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &ben_m,
         &speed_racer,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Cass Jones".into()]),
         }),
     );
-    graph.link_to(&lilly_w, &speed_racer, RelationType::Directed);
-    graph.link_to(&lana_w, &speed_racer, RelationType::Directed);
-    graph.link_to(&lilly_w, &speed_racer, RelationType::Wrote);
-    graph.link_to(&lana_w, &speed_racer, RelationType::Wrote);
-    graph.link_to(&joel_s, &speed_racer, RelationType::Produced);
+    link(&mut graph, &lilly_w, &speed_racer, RelationType::Directed);
+    link(&mut graph, &lana_w, &speed_racer, RelationType::Directed);
+    link(&mut graph, &lilly_w, &speed_racer, RelationType::Wrote);
+    link(&mut graph, &lana_w, &speed_racer, RelationType::Wrote);
+    link(&mut graph, &joel_s, &speed_racer, RelationType::Produced);
 
     let ninja_assassin = graph.add(NodeType::Movie(Movie {
         title: "Ninja Assassin".into(),
@@ -1534,38 +1790,52 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Naomie Harris".into(),
         born: 0,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &rain,
         &ninja_assassin,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Raizo".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &naomie_h,
         &ninja_assassin,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Mika Coretti".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &rick_y,
         &ninja_assassin,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Takeshi".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &ben_m,
         &ninja_assassin,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Ryan Maslow".into()]),
         }),
     );
-    graph.link_to(&james_m, &ninja_assassin, RelationType::Directed);
-    graph.link_to(&lilly_w, &ninja_assassin, RelationType::Produced);
-    graph.link_to(&lana_w, &ninja_assassin, RelationType::Produced);
-    graph.link_to(&joel_s, &ninja_assassin, RelationType::Produced);
+    link(
+        &mut graph,
+        &james_m,
+        &ninja_assassin,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &lilly_w,
+        &ninja_assassin,
+        RelationType::Produced,
+    );
+    link(&mut graph, &lana_w, &ninja_assassin, RelationType::Produced);
+    link(&mut graph, &joel_s, &ninja_assassin, RelationType::Produced);
 
     let the_green_mile = graph.add(NodeType::Movie(Movie {
         title: "The Green Mile".into(),
@@ -1596,63 +1866,76 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "'Frank Darabont'".into(),
         born: 1959,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Paul Edgecomb".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &michael_d,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["John Coffey".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &david_m,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Brutus \"Brutal\" Howell".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &bonnie_h,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jan Edgecomb".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &james_c,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Warden Hal Moores".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &sam_r,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["\"Wild Bill\" Wharton".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &gary_s,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Burt Hammersmith".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &patricia_c,
         &the_green_mile,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Melinda Moores".into()]),
         }),
     );
-    graph.link_to(&frank_d, &the_green_mile, RelationType::Directed);
+    link(
+        &mut graph,
+        &frank_d,
+        &the_green_mile,
+        RelationType::Directed,
+    );
 
     let frost_nixon = graph.add(NodeType::Movie(Movie {
         title: "Frost/Nixon".into(),
@@ -1671,42 +1954,47 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Oliver Platt".into(),
         born: 1960,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &frank_l,
         &frost_nixon,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Richard Nixon".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &michael_s,
         &frost_nixon,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["David Frost".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kevin_b,
         &frost_nixon,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jack Brennan".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &oliver_p,
         &frost_nixon,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Bob Zelnick".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &sam_r,
         &frost_nixon,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["James Reston, Jr.".into()]),
         }),
     );
-    graph.link_to(&ron_h, &frost_nixon, RelationType::Directed);
+    link(&mut graph, &ron_h, &frost_nixon, RelationType::Directed);
 
     let hoffa = graph.add(NodeType::Movie(Movie {
         title: "Hoffa".into(),
@@ -1721,35 +2009,39 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "John C. Reilly".into(),
         born: 1965,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &jack_n,
         &hoffa,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Hoffa".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &danny_d,
         &hoffa,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Robert \"Bobby\" Ciaro".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jtw,
         &hoffa,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Frank Fitzsimmons".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &john_r,
         &hoffa,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Peter \"Pete\" Connelly".into()]),
         }),
     );
-    graph.link_to(&danny_d, &hoffa, RelationType::Directed);
+    link(&mut graph, &danny_d, &hoffa, RelationType::Directed);
 
     let apollo13 = graph.add(NodeType::Movie(Movie {
         title: "Apollo 13".into(),
@@ -1764,42 +2056,47 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Bill Paxton".into(),
         born: 1955,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &apollo13,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jim Lovell".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &kevin_b,
         &apollo13,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jack Swigert".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &ed_h,
         &apollo13,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Gene Kranz".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &bill_pax,
         &apollo13,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Fred Haise".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &gary_s,
         &apollo13,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Ken Mattingly".into()]),
         }),
     );
-    graph.link_to(&ron_h, &apollo13, RelationType::Directed);
+    link(&mut graph, &ron_h, &apollo13, RelationType::Directed);
 
     let twister = graph.add(NodeType::Movie(Movie {
         title: "Twister".into(),
@@ -1814,35 +2111,39 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Jan de Bont".into(),
         born: 1943,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &bill_pax,
         &twister,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Bill Harding".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &helen_h,
         &twister,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dr. Jo Harding".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &zach_g,
         &twister,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Eddie".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &philip_h,
         &twister,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dustin \"Dusty\" Davis".into()]),
         }),
     );
-    graph.link_to(&jan_b, &twister, RelationType::Directed);
+    link(&mut graph, &jan_b, &twister, RelationType::Directed);
 
     let cast_away = graph.add(NodeType::Movie(Movie {
         title: "Cast Away".into(),
@@ -1853,21 +2154,23 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Robert Zemeckis".into(),
         born: 1951,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &cast_away,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Chuck Noland".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &helen_h,
         &cast_away,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Kelly Frears".into()]),
         }),
     );
-    graph.link_to(&robert_z, &cast_away, RelationType::Directed);
+    link(&mut graph, &robert_z, &cast_away, RelationType::Directed);
 
     let one_flew_overthe_cuckoos_nest = graph.add(NodeType::Movie(Movie {
         title: "One Flew Over the Cuckoo's Nest".into(),
@@ -1878,21 +2181,24 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Milos Forman".into(),
         born: 1932,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &jack_n,
         &one_flew_overthe_cuckoos_nest,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Randle McMurphy".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &danny_d,
         &one_flew_overthe_cuckoos_nest,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Martini".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &milos_f,
         &one_flew_overthe_cuckoos_nest,
         RelationType::Directed,
@@ -1911,30 +2217,48 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Nancy Meyers".into(),
         born: 1949,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &jack_n,
         &somethings_gotta_give,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Harry Sanborn".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &diane_k,
         &somethings_gotta_give,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Erica Barry".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &keanu,
         &somethings_gotta_give,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Julian Mercer".into()]),
         }),
     );
-    graph.link_to(&nancy_m, &somethings_gotta_give, RelationType::Directed);
-    graph.link_to(&nancy_m, &somethings_gotta_give, RelationType::Produced);
-    graph.link_to(&nancy_m, &somethings_gotta_give, RelationType::Wrote);
+    link(
+        &mut graph,
+        &nancy_m,
+        &somethings_gotta_give,
+        RelationType::Directed,
+    );
+    link(
+        &mut graph,
+        &nancy_m,
+        &somethings_gotta_give,
+        RelationType::Produced,
+    );
+    link(
+        &mut graph,
+        &nancy_m,
+        &somethings_gotta_give,
+        RelationType::Wrote,
+    );
 
     let bicentennial_man = graph.add(NodeType::Movie(Movie {
         title: "Bicentennial Man".into(),
@@ -1945,56 +2269,72 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Chris Columbus".into(),
         born: 1958,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &robin,
         &bicentennial_man,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Andrew Marin".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &oliver_p,
         &bicentennial_man,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Rupert Burns".into()]),
         }),
     );
-    graph.link_to(&chris_c, &bicentennial_man, RelationType::Directed);
+    link(
+        &mut graph,
+        &chris_c,
+        &bicentennial_man,
+        RelationType::Directed,
+    );
 
     let charlie_wilsons_war = graph.add(NodeType::Movie(Movie { title: "Charlie Wilson's War".into(), released: 2007, tagline: "A stiff drink. A little mascara. A lot of nerve. Who said they couldn't bring down the Soviet empire.".into()}));
     let julia_r = graph.add(NodeType::Person(Person {
         name: "Julia Roberts".into(),
         born: 1967,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &charlie_wilsons_war,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Rep. Charlie Wilson".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &julia_r,
         &charlie_wilsons_war,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Joanne Herring".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &philip_h,
         &charlie_wilsons_war,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Gust Avrakotos".into()]),
         }),
     );
-    graph.link_to(&mike_n, &charlie_wilsons_war, RelationType::Directed);
+    link(
+        &mut graph,
+        &mike_n,
+        &charlie_wilsons_war,
+        RelationType::Directed,
+    );
 
     let the_polar_express = graph.add(NodeType::Movie(Movie {
         title: "The Polar Express".into(),
         released: 2004,
         tagline: "This Holiday Season… Believe".into(),
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &the_polar_express,
         RelationType::ActedIn(ActedIn {
@@ -2003,7 +2343,12 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             ]),
         }),
     );
-    graph.link_to(&robert_z, &the_polar_express, RelationType::Directed);
+    link(
+        &mut graph,
+        &robert_z,
+        &the_polar_express,
+        RelationType::Directed,
+    );
 
     let aleagueof_their_own = graph.add(NodeType::Movie(Movie {
         title: "A League of Their Own".into(),
@@ -2026,49 +2371,60 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "Penny Marshall".into(),
         born: 1943,
     }));
-    graph.link_to(
+    link(
+        &mut graph,
         &tom_h,
         &aleagueof_their_own,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Jimmy Dugan".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &geena_d,
         &aleagueof_their_own,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Dottie Hinson".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &lori_p,
         &aleagueof_their_own,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Kit Keller".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &rosie_o,
         &aleagueof_their_own,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Doris Murphy".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &madonna,
         &aleagueof_their_own,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["\"All the Way\" Mae Mordabito".into()]),
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &bill_pax,
         &aleagueof_their_own,
         RelationType::ActedIn(ActedIn {
             roles: Vec::from_iter(["Bob Hinson".into()]),
         }),
     );
-    graph.link_to(&penny_m, &aleagueof_their_own, RelationType::Directed);
+    link(
+        &mut graph,
+        &penny_m,
+        &aleagueof_their_own,
+        RelationType::Directed,
+    );
 
     let paul_blythe = graph.add(NodeType::Person(Person {
         name: "Paul Blythe".into(),
@@ -2086,11 +2442,27 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
         name: "James Thompson".into(),
         born: 0,
     }));
-    graph.link_to(&james_thompson, &jessica_thompson, RelationType::Follows);
-    graph.link_to(&angela_scope, &jessica_thompson, RelationType::Follows);
-    graph.link_to(&paul_blythe, &angela_scope, RelationType::Follows);
+    link(
+        &mut graph,
+        &james_thompson,
+        &jessica_thompson,
+        RelationType::Follows,
+    );
+    link(
+        &mut graph,
+        &angela_scope,
+        &jessica_thompson,
+        RelationType::Follows,
+    );
+    link(
+        &mut graph,
+        &paul_blythe,
+        &angela_scope,
+        RelationType::Follows,
+    );
 
-    graph.link_to(
+    link(
+        &mut graph,
         &jessica_thompson,
         &cloud_atlas,
         RelationType::Reviewed(Review {
@@ -2098,7 +2470,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 95,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jessica_thompson,
         &the_replacements,
         RelationType::Reviewed(Review {
@@ -2106,7 +2479,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 65,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &james_thompson,
         &the_replacements,
         RelationType::Reviewed(Review {
@@ -2114,7 +2488,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 100,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &angela_scope,
         &the_replacements,
         RelationType::Reviewed(Review {
@@ -2122,7 +2497,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 62,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jessica_thompson,
         &unforgiven,
         RelationType::Reviewed(Review {
@@ -2130,7 +2506,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 85,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jessica_thompson,
         &the_birdcage,
         RelationType::Reviewed(Review {
@@ -2138,7 +2515,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 45,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &jessica_thompson,
         &the_da_vinci_code,
         RelationType::Reviewed(Review {
@@ -2146,7 +2524,8 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
             rating: 68,
         }),
     );
-    graph.link_to(
+    link(
+        &mut graph,
         &james_thompson,
         &the_da_vinci_code,
         RelationType::Reviewed(Review {
@@ -2157,3 +2536,15 @@ pub fn movie_graph() -> EmbeddedPropertyGraph<NodeType, RelationType> {
 
     graph
 }
+
+/// Links two nodes of the movie graph.
+fn link(
+    graph: &mut EmbeddedPropertyGraph<NodeType, RelationType>,
+    from: &NodeAddress,
+    to: &NodeAddress,
+    relation: RelationType,
+) {
+    graph
+        .link_to(from, to, relation)
+        .expect("the movie graph nodes exist");
+}
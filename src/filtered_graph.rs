@@ -0,0 +1,390 @@
+//! Provides a view of a graph restricted to a subset of its relations and nodes.
+
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::{GraphId, NodeAddress};
+use crate::node_relation::NodeRelationRef;
+
+/// Trait for selecting the relations that may be traversed.
+///
+/// Implemented for closures taking a relation.
+pub trait RelationFilter<R> {
+    /// Determines whether the specified relation may be traversed.
+    fn allows(&self, relation: &R) -> bool;
+}
+
+impl<R, F> RelationFilter<R> for F
+where
+    F: Fn(&R) -> bool,
+{
+    fn allows(&self, relation: &R) -> bool {
+        self(relation)
+    }
+}
+
+/// Trait for selecting the nodes that may be visited.
+///
+/// Implemented for closures taking a node's address and data.
+pub trait NodeFilter<N> {
+    /// Determines whether the specified node may be visited.
+    fn allows(&self, address: &NodeAddress, node: &N) -> bool;
+}
+
+impl<N, F> NodeFilter<N> for F
+where
+    F: Fn(&NodeAddress, &N) -> bool,
+{
+    fn allows(&self, address: &NodeAddress, node: &N) -> bool {
+        self(address, node)
+    }
+}
+
+/// A filter that allows all relations and nodes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AllowAll;
+
+impl<R> RelationFilter<R> for AllowAll {
+    fn allows(&self, _relation: &R) -> bool {
+        true
+    }
+}
+
+impl<N> NodeFilter<N> for AllowAll {
+    fn allows(&self, _address: &NodeAddress, _node: &N) -> bool {
+        true
+    }
+}
+
+/// A view of a graph that hides all relations and nodes rejected by a filter.
+///
+/// Since all path queries and traversals are implemented against the [`Graph`] trait,
+/// they can run on the view directly. Hidden relations are never yielded as neighbors,
+/// so a query does not even consider them, unlike relations with an infinite path cost.
+/// Hidden nodes are treated as if they were not part of the graph; relations leading to
+/// them are hidden as well, and addressing them fails with [`NodeAddressError::NodeNotFound`].
+#[derive(Debug, Clone, Copy)]
+pub struct FilteredGraph<'a, G, RF = AllowAll, NF = AllowAll> {
+    graph: &'a G,
+    relation_filter: RF,
+    node_filter: NF,
+}
+
+impl<'a, G> FilteredGraph<'a, G>
+where
+    G: Graph,
+{
+    /// Creates a view of the specified graph that initially hides nothing.
+    pub fn new(graph: &'a G) -> Self {
+        FilteredGraph {
+            graph,
+            relation_filter: AllowAll,
+            node_filter: AllowAll,
+        }
+    }
+}
+
+impl<'a, G, RF, NF> FilteredGraph<'a, G, RF, NF>
+where
+    G: Graph,
+    RF: RelationFilter<G::Relation>,
+    NF: NodeFilter<G::Node>,
+{
+    /// Hides all relations rejected by the specified filter, replacing any previous relation filter.
+    pub fn with_relation_filter<F>(self, filter: F) -> FilteredGraph<'a, G, F, NF>
+    where
+        F: RelationFilter<G::Relation>,
+    {
+        FilteredGraph {
+            graph: self.graph,
+            relation_filter: filter,
+            node_filter: self.node_filter,
+        }
+    }
+
+    /// Hides all nodes rejected by the specified filter, replacing any previous node filter.
+    pub fn with_node_filter<F>(self, filter: F) -> FilteredGraph<'a, G, RF, F>
+    where
+        F: NodeFilter<G::Node>,
+    {
+        FilteredGraph {
+            graph: self.graph,
+            relation_filter: self.relation_filter,
+            node_filter: filter,
+        }
+    }
+
+    /// Gets the underlying graph.
+    pub fn graph(&self) -> &'a G {
+        self.graph
+    }
+
+    /// Determines whether the node at the specified address is part of the view.
+    ///
+    /// Nodes missing from the underlying graph are reported as visible, so that queries
+    /// fail the same way they would on the underlying graph.
+    fn is_visible(&self, address: &NodeAddress) -> bool {
+        self.graph
+            .local_node_data_ref(address)
+            .map_or(true, |node| self.node_filter.allows(address, node))
+    }
+
+    /// Determines whether a relation to the specified neighbor is part of the view.
+    fn is_traversable(&self, neighbor: &NodeRelationRef<'_, G::Relation>) -> bool {
+        self.relation_filter.allows(neighbor.relation) && self.is_visible(neighbor.address)
+    }
+}
+
+impl<'a, G, RF, NF> Graph for FilteredGraph<'a, G, RF, NF>
+where
+    G: Graph,
+    RF: RelationFilter<G::Relation>,
+    NF: NodeFilter<G::Node>,
+{
+    type Node = G::Node;
+    type Relation = G::Relation;
+
+    fn iter_local_neighbors(
+        &self,
+        address: &NodeAddress,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, G::Relation>>, NodeAddressError> {
+        self.local_node_data_ref(address)?;
+        Ok(self
+            .graph
+            .iter_local_neighbors(address)?
+            .filter(|neighbor| self.is_traversable(neighbor)))
+    }
+
+    fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, G::Relation>>, NodeAddressError> {
+        self.local_node_data_ref(address)?;
+        Ok(self
+            .graph
+            .iter_local_neighbors_directed(address, direction)?
+            .filter(|neighbor| self.is_traversable(neighbor)))
+    }
+
+    fn local_node_data_ref(&self, address: &NodeAddress) -> Result<&G::Node, NodeAddressError> {
+        let node = self.graph.local_node_data_ref(address)?;
+        if self.node_filter.allows(address, node) {
+            Ok(node)
+        } else {
+            Err(NodeAddressError::NodeNotFound(address.clone()))
+        }
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &G::Node)> {
+        self.graph
+            .nodes()
+            .filter(|(address, node)| self.node_filter.allows(address, node))
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (NodeAddress, &G::Relation, NodeAddress)> {
+        self.graph.edges().filter(|(from, relation, to)| {
+            self.relation_filter.allows(relation) && self.is_visible(from) && self.is_visible(to)
+        })
+    }
+
+    fn id(&self) -> GraphId {
+        self.graph.id()
+    }
+
+    fn node_count(&self) -> usize {
+        self.nodes().count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edges().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "examples-london", feature = "examples-movies"))]
+    use super::*;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{
+        london_graph, ConnectionType, LondonGraphDistanceHeuristic, LondonGraphStationsCost,
+        Station,
+    };
+    #[cfg(feature = "examples-movies")]
+    use crate::examples::movie_graph::{movie_graph, RelationType};
+    #[cfg(feature = "examples-london")]
+    use crate::node_relation::NodePathLink;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::astar::AStarSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bellman_ford::BellmanFordSearch;
+    #[cfg(any(feature = "examples-london", feature = "examples-movies"))]
+    use crate::path_queries::bfs::BreadthFirstSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bidirectional_astar::BidirectionalAStarSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bidirectional_bfs::BidirectionalBreadthFirstSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::dfs::DepthFirstSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::dijkstra::DijkstraSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::johnson::JohnsonSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::yen::YenSearch;
+
+    #[cfg(feature = "examples-london")]
+    fn by_road(relation: &ConnectionType) -> bool {
+        matches!(relation, ConnectionType::Taxi | ConnectionType::Bus)
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn relation_filter() {
+        let graph = london_graph();
+        let view = FilteredGraph::new(&graph).with_relation_filter(by_road);
+        assert_eq!(view.node_count(), graph.node_count());
+        assert!(view.edge_count() < graph.edge_count());
+
+        let start = NodeAddress::from_local(graph.id(), 0);
+        let target = NodeAddress::from_local(graph.id(), 198);
+        let cost = LondonGraphStationsCost;
+        let heuristic = LondonGraphDistanceHeuristic;
+        let paths: Vec<Vec<NodePathLink<ConnectionType>>> = vec![
+            AStarSearch
+                .shortest_path(&view, start.clone(), target.clone(), &cost, &heuristic)
+                .unwrap(),
+            BellmanFordSearch
+                .shortest_path(&view, start.clone(), target.clone(), &cost)
+                .unwrap(),
+            BreadthFirstSearch
+                .shortest_path(&view, start.clone(), target.clone())
+                .unwrap(),
+            BidirectionalAStarSearch
+                .shortest_path(&view, start.clone(), target.clone(), &cost, &heuristic)
+                .unwrap(),
+            BidirectionalBreadthFirstSearch
+                .shortest_path(&view, start.clone(), target.clone())
+                .unwrap(),
+            DepthFirstSearch
+                .find_path(&view, start.clone(), target.clone())
+                .unwrap(),
+            DijkstraSearch
+                .shortest_path(&view, start.clone(), target.clone(), &cost)
+                .unwrap(),
+            JohnsonSearch.all_pairs(&view, &cost).unwrap()[&start].path_to(&target),
+            YenSearch
+                .k_shortest_paths(&view, start.clone(), target.clone(), 1, &cost, &heuristic)
+                .unwrap()
+                .remove(0)
+                .path,
+        ];
+        for path in paths {
+            assert_eq!(path.first().unwrap().address, start);
+            assert_eq!(path.last().unwrap().address, target);
+            assert!(path[1..]
+                .iter()
+                .all(|link| by_road(link.relation.as_ref().unwrap())));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn node_filter() {
+        // Station 13 lies on the shortest path from station 1 to station 199.
+        let graph = london_graph();
+        let closed = NodeAddress::from_local(graph.id(), 12);
+        let view = FilteredGraph::new(&graph)
+            .with_node_filter(|address: &NodeAddress, _: &Station| *address != closed);
+        assert_eq!(view.node_count(), graph.node_count() - 1);
+        assert!(view.nodes().all(|(address, _)| address != closed));
+        assert!(view
+            .edges()
+            .all(|(from, _, to)| from != closed && to != closed));
+
+        let start = NodeAddress::from_local(graph.id(), 0);
+        let target = NodeAddress::from_local(graph.id(), 198);
+        let unfiltered = BreadthFirstSearch
+            .shortest_path(&graph, start.clone(), target.clone())
+            .unwrap();
+        assert!(unfiltered.iter().any(|link| link.address == closed));
+        let detour = BreadthFirstSearch
+            .shortest_path(&view, start.clone(), target)
+            .unwrap();
+        assert!(!detour.is_empty());
+        assert!(detour.iter().all(|link| link.address != closed));
+
+        assert!(matches!(
+            BreadthFirstSearch.shortest_path(&view, start, closed.clone()),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-movies")]
+    fn movie_roles() {
+        // Only acting credits connect people to movies.
+        let graph = movie_graph();
+        let acting = FilteredGraph::new(&graph).with_relation_filter(|relation: &RelationType| {
+            matches!(relation, RelationType::ActedIn(_))
+        });
+        assert!(acting.edge_count() > 0);
+        assert!(acting.edge_count() < graph.edge_count());
+        for (from, _, _) in acting.edges() {
+            assert!(acting
+                .iter_local_neighbors(&from)
+                .unwrap()
+                .all(|neighbor| matches!(neighbor.relation, RelationType::ActedIn(_))));
+        }
+    }
+
+    #[cfg(feature = "examples-movies")]
+    fn well_rated(relation: &RelationType) -> bool {
+        match relation {
+            RelationType::Reviewed(review) => review.rating() > 60,
+            _ => true,
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-movies")]
+    fn review_rating_filter() {
+        let graph = movie_graph();
+        let view = FilteredGraph::new(&graph).with_relation_filter(well_rated);
+
+        // The only way to reach a movie with a poor review from its reviewer is the review itself.
+        let (reviewer, _, movie) = graph
+            .edges()
+            .find(|(_, relation, _)| !well_rated(relation))
+            .unwrap();
+        let unfiltered = BreadthFirstSearch
+            .shortest_path(&graph, reviewer.clone(), movie.clone())
+            .unwrap();
+        assert_eq!(unfiltered.len(), 2);
+        assert!(BreadthFirstSearch
+            .shortest_path(&view, reviewer, movie)
+            .unwrap()
+            .is_empty());
+
+        // Paths to reviewed movies, e.g. along followers, only use well rated reviews.
+        let reviewed: Vec<_> = graph
+            .edges()
+            .filter(|(_, relation, _)| matches!(relation, RelationType::Reviewed(_)))
+            .map(|(_, _, movie)| movie)
+            .collect();
+        let mut reviews_used = 0;
+        for (start, _) in graph.nodes() {
+            for movie in &reviewed {
+                let path = BreadthFirstSearch
+                    .shortest_path(&view, start.clone(), movie.clone())
+                    .unwrap();
+                for link in path.iter().skip(1) {
+                    if let Some(RelationType::Reviewed(review)) = &link.relation {
+                        assert!(review.rating() > 60);
+                        reviews_used += 1;
+                    }
+                }
+            }
+        }
+        assert!(reviews_used > 0);
+    }
+}
@@ -0,0 +1,321 @@
+//! Provides maximum flow and minimum cut computations.
+
+use crate::errors::{MaxFlowError, NodeAddressError};
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use std::collections::{HashMap, VecDeque};
+
+/// Trait for relation capacities.
+pub trait Capacity<N, R> {
+    /// Provides the maximum amount of flow that can pass along a relation.
+    ///
+    /// Capacities must be finite; infinite or NaN capacities are rejected.
+    /// Relations with a capacity of zero or less cannot carry any flow.
+    fn capacity(&self, from: &N, to: &N, relation: &R) -> f32;
+}
+
+/// The flow along a single relation.
+#[derive(Debug, Clone)]
+pub struct EdgeFlow<R> {
+    /// The node the relation originates from.
+    pub from: NodeAddress,
+    /// The relation.
+    pub relation: R,
+    /// The node the relation leads to.
+    pub to: NodeAddress,
+    /// The amount of flow along the relation.
+    pub flow: f32,
+}
+
+/// A maximum flow between two nodes, together with a minimum cut.
+#[derive(Debug, Clone)]
+pub struct MaxFlow<R> {
+    /// The total amount of flow from the source to the sink.
+    pub value: f32,
+    /// The flow along every relation of the graph, in the order the graph yields its relations.
+    pub flows: Vec<EdgeFlow<R>>,
+    /// The nodes on the source side of a minimum cut, i.e. those still reachable from the
+    /// source in the residual graph, in the order the graph yields its nodes.
+    pub source_side: Vec<NodeAddress>,
+    /// The remaining nodes, including the sink, in the order the graph yields its nodes.
+    pub sink_side: Vec<NodeAddress>,
+}
+
+/// Determines a maximum flow from the `source` to the `sink` node using the Edmonds-Karp algorithm.
+///
+/// The relations crossing from the [`MaxFlow::source_side`] to the [`MaxFlow::sink_side`]
+/// form a minimum cut; their capacities sum up to the flow value.
+///
+/// ## Errors
+///
+/// Fails with [`MaxFlowError::SourceIsSink`] if `source` and `sink` are the same node,
+/// with [`MaxFlowError::InvalidCapacity`] if a relation has an infinite or NaN capacity,
+/// or if `source` or `sink` does not address a node of the graph.
+pub fn edmonds_karp<G, C>(
+    graph: &G,
+    source: NodeAddress,
+    sink: NodeAddress,
+    capacity: &C,
+) -> Result<MaxFlow<G::Relation>, MaxFlowError>
+where
+    G: Graph,
+    G::Relation: Clone,
+    C: Capacity<G::Node, G::Relation>,
+{
+    graph.local_node_data_ref(&source)?;
+    graph.local_node_data_ref(&sink)?;
+    if source == sink {
+        return Err(MaxFlowError::SourceIsSink(source));
+    }
+
+    let nodes: Vec<_> = graph.nodes().map(|(address, _)| address).collect();
+    let index: HashMap<_, _> = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, address)| (address.clone(), index))
+        .collect();
+    let lookup = |address: &NodeAddress| {
+        index
+            .get(address)
+            .copied()
+            .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone()))
+    };
+
+    // Every relation is represented by a forward arc `2 * i` and a reverse arc `2 * i + 1`;
+    // the residual capacity of the reverse arc is the flow along the relation.
+    let mut arcs = Vec::with_capacity(2 * graph.edge_count());
+    let mut residual = Vec::with_capacity(2 * graph.edge_count());
+    let mut outgoing = vec![Vec::new(); nodes.len()];
+    for (from, relation, to) in graph.edges() {
+        let value = capacity.capacity(
+            graph.local_node_data_ref(&from)?,
+            graph.local_node_data_ref(&to)?,
+            relation,
+        );
+        if !value.is_finite() {
+            return Err(MaxFlowError::InvalidCapacity {
+                from,
+                to,
+                capacity: value,
+            });
+        }
+
+        let (from, to) = (lookup(&from)?, lookup(&to)?);
+        outgoing[from].push(arcs.len());
+        arcs.push(to);
+        residual.push(value.max(0.0));
+        outgoing[to].push(arcs.len());
+        arcs.push(from);
+        residual.push(0.0);
+    }
+
+    let (source, sink) = (index[&source], index[&sink]);
+    let mut value = 0.0;
+    loop {
+        // Find a shortest augmenting path, remembering the arc each node was reached through.
+        let mut reached_by: Vec<Option<usize>> = vec![None; nodes.len()];
+        let mut queue = VecDeque::from([source]);
+        while let Some(current) = queue.pop_front() {
+            for &arc in &outgoing[current] {
+                let next = arcs[arc];
+                if residual[arc] > 0.0 && next != source && reached_by[next].is_none() {
+                    reached_by[next] = Some(arc);
+                    queue.push_back(next);
+                }
+            }
+            if reached_by[sink].is_some() {
+                break;
+            }
+        }
+        if reached_by[sink].is_none() {
+            break;
+        }
+
+        // The bottleneck arc ends up with a residual capacity of exactly zero.
+        let path = std::iter::successors(reached_by[sink], |&arc| reached_by[arcs[arc ^ 1]]);
+        let bottleneck = path
+            .clone()
+            .map(|arc| residual[arc])
+            .fold(f32::INFINITY, f32::min);
+        for arc in path {
+            residual[arc] -= bottleneck;
+            residual[arc ^ 1] += bottleneck;
+        }
+        value += bottleneck;
+    }
+
+    // The source side of the minimum cut is everything reachable in the residual graph.
+    let mut on_source_side = vec![false; nodes.len()];
+    on_source_side[source] = true;
+    let mut queue = VecDeque::from([source]);
+    while let Some(current) = queue.pop_front() {
+        for &arc in &outgoing[current] {
+            let next = arcs[arc];
+            if residual[arc] > 0.0 && !on_source_side[next] {
+                on_source_side[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let flows = graph
+        .edges()
+        .enumerate()
+        .map(|(edge, (from, relation, to))| EdgeFlow {
+            from,
+            relation: relation.clone(),
+            to,
+            flow: residual[2 * edge + 1],
+        })
+        .collect();
+    let (source_side, sink_side) = nodes
+        .into_iter()
+        .enumerate()
+        .partition::<Vec<_>, _>(|(index, _)| on_source_side[*index]);
+
+    Ok(MaxFlow {
+        value,
+        flows,
+        source_side: source_side
+            .into_iter()
+            .map(|(_, address)| address)
+            .collect(),
+        sink_side: sink_side.into_iter().map(|(_, address)| address).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, ConnectionType, Station};
+    use crate::test_support::RelationValue;
+    #[cfg(feature = "examples-london")]
+    use std::collections::HashSet;
+
+    #[test]
+    fn it_works() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [s, v1, v2, v3, v4, t] = ["s", "v1", "v2", "v3", "v4", "t"].map(|name| graph.add(name));
+        for (from, to, capacity) in [
+            (&s, &v1, 16.0),
+            (&s, &v2, 13.0),
+            (&v1, &v3, 12.0),
+            (&v2, &v1, 4.0),
+            (&v2, &v4, 14.0),
+            (&v3, &v2, 9.0),
+            (&v3, &t, 20.0),
+            (&v4, &v3, 7.0),
+            (&v4, &t, 4.0),
+        ] {
+            graph.link_to(from, to, capacity).unwrap();
+        }
+
+        let flow = edmonds_karp(&graph, s.clone(), t.clone(), &RelationValue).unwrap();
+        assert_eq!(flow.value, 23.0);
+        assert_eq!(flow.source_side, [s.clone(), v1, v2, v4]);
+        assert_eq!(flow.sink_side, [v3, t.clone()]);
+
+        // Flow is conserved at every node except the source and the sink.
+        let mut balance: HashMap<NodeAddress, f32> = HashMap::new();
+        for edge in &flow.flows {
+            assert!(edge.flow >= 0.0 && edge.flow <= edge.relation);
+            *balance.entry(edge.from.clone()).or_default() -= edge.flow;
+            *balance.entry(edge.to.clone()).or_default() += edge.flow;
+        }
+        for (address, balance) in balance {
+            let expected = match address {
+                _ if address == s => -23.0,
+                _ if address == t => 23.0,
+                _ => 0.0,
+            };
+            assert_eq!(balance, expected);
+        }
+    }
+
+    /// Assumes that underground lines carry the most passengers.
+    #[cfg(feature = "examples-london")]
+    struct ConnectionCapacity;
+
+    #[cfg(feature = "examples-london")]
+    impl Capacity<Station, ConnectionType> for ConnectionCapacity {
+        fn capacity(&self, _from: &Station, _to: &Station, relation: &ConnectionType) -> f32 {
+            match relation {
+                ConnectionType::Underground => 10.0,
+                ConnectionType::Bus => 4.0,
+                ConnectionType::Ferry => 2.0,
+                ConnectionType::Taxi => 1.0,
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn london_capacity() {
+        let graph = london_graph();
+        let source = NodeAddress::from_local(graph.id(), 0);
+        let sink = NodeAddress::from_local(graph.id(), 198);
+        let flow = edmonds_karp(&graph, source.clone(), sink.clone(), &ConnectionCapacity).unwrap();
+        assert!(flow.value > 0.0);
+
+        // The capacity of the minimum cut equals the flow value.
+        let source_side: HashSet<_> = flow.source_side.iter().collect();
+        assert!(source_side.contains(&source));
+        assert!(!source_side.contains(&sink));
+        let cut: f32 = flow
+            .flows
+            .iter()
+            .filter(|edge| source_side.contains(&edge.from) && !source_side.contains(&edge.to))
+            .map(|edge| {
+                let from = graph.node(&edge.from).unwrap();
+                let to = graph.node(&edge.to).unwrap();
+                ConnectionCapacity.capacity(from, to, &edge.relation)
+            })
+            .sum();
+        assert_eq!(cut, flow.value);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = edmonds_karp(
+            &graph,
+            NodeAddress::from_local(graph.id(), 0),
+            NodeAddress::from_local(graph.id(), 1000),
+            &ConnectionCapacity,
+        );
+        assert!(matches!(
+            result,
+            Err(MaxFlowError::InvalidNode(NodeAddressError::NodeNotFound(_)))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn source_is_sink() {
+        let graph = london_graph();
+        let station = NodeAddress::from_local(graph.id(), 0);
+        let result = edmonds_karp(&graph, station.clone(), station, &ConnectionCapacity);
+        assert!(matches!(result, Err(MaxFlowError::SourceIsSink(_))));
+    }
+
+    #[test]
+    fn invalid_capacity() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [s, v, t] = ["s", "v", "t"].map(|name| graph.add(name));
+        graph.link_to(&s, &v, 1.0).unwrap();
+        graph.link_to(&v, &t, 2.0).unwrap();
+
+        for capacity in [f32::INFINITY, f32::NAN] {
+            graph.link_to(&s, &t, capacity).unwrap();
+            let result = edmonds_karp(&graph, s.clone(), t.clone(), &RelationValue);
+            let Err(MaxFlowError::InvalidCapacity { from, to, .. }) = result else {
+                panic!("expected an invalid capacity");
+            };
+            assert_eq!((from, to), (s.clone(), t.clone()));
+            graph.unlink(&s, &t, |_| true).unwrap();
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Provides the [`Graph`] trait shared by all graph storage types.
+
+use crate::errors::NodeAddressError;
+use crate::node_address::{GraphId, NodeAddress};
+use crate::node_relation::NodeRelationRef;
+
+/// Common read access to a graph, independent of its memory layout.
+///
+/// All path queries are implemented against this trait, allowing the same query
+/// to run on e.g. a [`PropertyGraph`](crate::property_graph::PropertyGraph) as well as on an
+/// [`EmbeddedPropertyGraph`](crate::embedded_property_graph::EmbeddedPropertyGraph).
+pub trait Graph {
+    /// The type of data stored in each node.
+    type Node;
+
+    /// The type of relation between nodes.
+    type Relation;
+
+    /// Iterates the neighbors of a node given its [`NodeAddress`] under the condition that the node is locally available.
+    fn iter_local_neighbors(
+        &self,
+        address: &NodeAddress,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, Self::Relation>>, NodeAddressError>;
+
+    /// Iterates the neighbors of a node along relations of the specified [`Direction`].
+    ///
+    /// For incoming relations, the yielded address is the one of the node the relation originates from.
+    fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, Self::Relation>>, NodeAddressError>;
+
+    /// Gets a node's data given its [`NodeAddress`] under the condition that the node is locally available.
+    fn local_node_data_ref(&self, address: &NodeAddress) -> Result<&Self::Node, NodeAddressError>;
+
+    /// Iterates all nodes of the graph, together with their addresses.
+    fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &Self::Node)>;
+
+    /// Iterates all relations of the graph as `(from, relation, to)` triples.
+    fn edges(&self) -> impl Iterator<Item = (NodeAddress, &Self::Relation, NodeAddress)>;
+
+    /// Gets the id of the graph, which is part of every address of its nodes.
+    ///
+    /// Addresses with a different id are rejected with [`NodeAddressError::ForeignGraph`].
+    fn id(&self) -> GraphId;
+
+    /// Gets the number of nodes in the graph.
+    fn node_count(&self) -> usize;
+
+    /// Gets the number of relations in the graph.
+    fn edge_count(&self) -> usize;
+}
+
+/// The direction in which relations are followed when iterating the neighbors of a node.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Direction {
+    /// Follows relations from the node to its neighbors.
+    Outgoing,
+    /// Follows relations from the neighbors to the node.
+    Incoming,
+    /// Follows relations in both directions; outgoing relations are yielded first.
+    Both,
+}
+
+impl Direction {
+    /// Determines whether outgoing relations are followed in this direction.
+    pub const fn includes_outgoing(self) -> bool {
+        matches!(self, Direction::Outgoing | Direction::Both)
+    }
+
+    /// Determines whether incoming relations are followed in this direction.
+    pub const fn includes_incoming(self) -> bool {
+        matches!(self, Direction::Incoming | Direction::Both)
+    }
+}
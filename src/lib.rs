@@ -1,7 +1,20 @@
-mod embedded_property_graph;
-mod errors;
-mod examples;
-mod node_address;
-mod node_relation;
-mod path_queries;
-mod property_graph;
+pub mod centrality;
+pub mod community;
+pub mod components;
+pub mod csr_graph;
+pub mod embedded_property_graph;
+pub mod errors;
+pub mod examples;
+pub mod filtered_graph;
+pub mod flow;
+pub mod graph;
+pub mod node_address;
+pub mod node_relation;
+mod node_slots;
+pub mod path_queries;
+pub mod property_graph;
+pub mod spanning_tree;
+#[cfg(test)]
+mod test_support;
+pub mod topological;
+pub mod traversal;
@@ -1,21 +1,88 @@
+#[cfg(feature = "serde")]
+use crate::errors::NodeAddressError;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Identifies a graph instance, so that addresses of one graph are rejected by all others.
+///
+/// Every new graph receives a process-wide unique id, and so does every deserialized graph;
+/// addresses of the graph it was serialized from are rejected by it.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphId(u64);
+
+impl GraphId {
+    /// Creates an id that differs from all ids created before.
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        GraphId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Display for GraphId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "graph #{}", self.0)
+    }
+}
 
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NodeAddress {
-    Local(usize),
+    /// A node in the local graph, identified by the graph's id, its slot index and the generation of that slot.
+    ///
+    /// Slots are recycled when nodes are removed; the generation is incremented on every removal
+    /// so that a stale address can be told apart from an address of the node now occupying the slot.
+    Local {
+        graph: GraphId,
+        index: usize,
+        generation: u32,
+    },
 }
 
 impl NodeAddress {
-    pub const fn from_local(node_id: usize) -> Self {
-        NodeAddress::Local(node_id)
+    /// Creates the address of a node of the specified graph in the first generation of its slot.
+    pub const fn from_local(graph: GraphId, node_id: usize) -> Self {
+        NodeAddress::from_local_generation(graph, node_id, 0)
+    }
+
+    /// Creates the address of a node of the specified graph in the specified generation of its slot.
+    pub const fn from_local_generation(graph: GraphId, node_id: usize, generation: u32) -> Self {
+        NodeAddress::Local {
+            graph,
+            index: node_id,
+            generation,
+        }
+    }
+
+    /// Moves an address of the `from` graph over to the `to` graph, keeping its slot and generation.
+    ///
+    /// Fails if the address does not belong to the `from` graph.
+    #[cfg(feature = "serde")]
+    pub(crate) fn rebind(&mut self, from: GraphId, to: GraphId) -> Result<(), NodeAddressError> {
+        match self {
+            NodeAddress::Local { graph, .. } if *graph == from => {
+                *graph = to;
+                Ok(())
+            }
+            _ => Err(NodeAddressError::ForeignGraph(self.clone())),
+        }
     }
 }
 
 impl Display for NodeAddress {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            NodeAddress::Local(id) => write!(f, "local node #{id}"),
+            NodeAddress::Local {
+                graph,
+                index,
+                generation,
+            } => {
+                write!(
+                    f,
+                    "local node #{index} (generation {generation}) of {graph}"
+                )
+            }
         }
     }
 }
@@ -23,7 +90,15 @@ impl Display for NodeAddress {
 impl Hash for NodeAddress {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
-            NodeAddress::Local(id) => id.hash(state),
+            NodeAddress::Local {
+                graph,
+                index,
+                generation,
+            } => {
+                graph.hash(state);
+                index.hash(state);
+                generation.hash(state);
+            }
         }
     }
 }
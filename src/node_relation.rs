@@ -1,10 +1,11 @@
-//! Provides the [`NodePathLink`] and [`NodeRelation`] types.
+//! Provides the [`NodePathLink`], [`NodeRelation`] and [`NodeRelationRef`] types.
 
 use crate::node_address::NodeAddress;
 
 /// Describes a path link between two nodes used during graph traversals.
 /// It describes a node, as well as the relation that was traversed in order to get to it.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodePathLink<R>
 where
     R: Clone,
@@ -18,6 +19,7 @@ where
 
 /// An internal representation of a node relation.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NodeRelation<R> {
     /// The relation that leads to the addressed node.
     pub relation: R,
@@ -25,6 +27,101 @@ pub struct NodeRelation<R> {
     pub address: NodeAddress,
 }
 
+/// A back-reference to a relation, kept by the node the relation leads to.
+#[derive(Debug, Clone)]
+pub(crate) struct IncomingRelation {
+    /// The address of the node the relation originates from.
+    pub source: NodeAddress,
+    /// The position of the relation among the outgoing relations of the source.
+    pub position: usize,
+}
+
+/// Removes the relations for which `remove` returns `true`, keeping the order of the others.
+///
+/// Returns the removed relations in their former order, along with the new position of every
+/// relation by its former position, or `None` if it was removed.
+pub(crate) fn remove_relations<R, F>(
+    outgoing: &mut Vec<NodeRelation<R>>,
+    mut remove: F,
+) -> (Vec<NodeRelation<R>>, Vec<Option<usize>>)
+where
+    F: FnMut(&NodeRelation<R>) -> bool,
+{
+    let mut removed = Vec::new();
+    let mut positions = Vec::with_capacity(outgoing.len());
+    let mut kept = Vec::with_capacity(outgoing.len());
+    for relation in outgoing.drain(..) {
+        if remove(&relation) {
+            positions.push(None);
+            removed.push(relation);
+        } else {
+            positions.push(Some(kept.len()));
+            kept.push(relation);
+        }
+    }
+    *outgoing = kept;
+    (removed, positions)
+}
+
+/// Updates the back-references to the relations of the `source` node after some of them were
+/// removed, given the new `positions` returned by [`remove_relations`].
+pub(crate) fn update_incoming(
+    incoming: &mut Vec<IncomingRelation>,
+    source: &NodeAddress,
+    positions: &[Option<usize>],
+) {
+    incoming.retain_mut(|relation| {
+        if relation.source != *source {
+            return true;
+        }
+        match positions[relation.position] {
+            Some(position) => {
+                relation.position = position;
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+/// A borrowed view of a node relation, as yielded when iterating the neighbors of a node.
+#[derive(Debug)]
+pub struct NodeRelationRef<'a, R> {
+    /// The relation between the nodes.
+    pub relation: &'a R,
+    /// The address of the node on the other end of the relation.
+    pub address: &'a NodeAddress,
+}
+
+impl<'a, R> Clone for NodeRelationRef<'a, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, R> Copy for NodeRelationRef<'a, R> {}
+
+impl<'a, R> From<&'a NodeRelation<R>> for NodeRelationRef<'a, R> {
+    fn from(value: &'a NodeRelation<R>) -> Self {
+        NodeRelationRef {
+            relation: &value.relation,
+            address: &value.address,
+        }
+    }
+}
+
+impl<R> From<NodeRelationRef<'_, R>> for NodePathLink<R>
+where
+    R: Clone,
+{
+    fn from(value: NodeRelationRef<'_, R>) -> Self {
+        NodePathLink {
+            relation: Some(value.relation.clone()),
+            address: value.address.clone(),
+        }
+    }
+}
+
 impl<R> From<&NodeRelation<R>> for NodePathLink<R>
 where
     R: Clone,
@@ -0,0 +1,277 @@
+//! Provides generation-checked node storage shared by the graph types.
+
+use crate::errors::NodeAddressError;
+use crate::node_address::{GraphId, NodeAddress};
+
+/// A slot-based node store that recycles the slots of removed nodes.
+///
+/// Every slot carries a generation counter that is incremented whenever its node is removed.
+/// Addresses handed out by [`NodeSlots::insert_with`] capture the generation at the time of insertion,
+/// so that lookups through a stale address fail instead of resolving to whatever node
+/// currently occupies the slot. Addresses also capture the id of the store, so that lookups
+/// through an address of another graph fail as well.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub(crate) struct NodeSlots<N> {
+    /// The id of the graph owning the store.
+    graph: GraphId,
+    slots: Vec<Slot<N>>,
+    /// Indexes of vacant slots, available for reuse.
+    free: Vec<usize>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Slot<N> {
+    generation: u32,
+    node: Option<N>,
+}
+
+impl<N> NodeSlots<N> {
+    /// Stores a node in a vacant slot, or a new one if none is available.
+    ///
+    /// The `create` function is passed the slot index the node will be stored at.
+    pub fn insert_with<F>(&mut self, create: F) -> NodeAddress
+    where
+        F: FnOnce(usize) -> N,
+    {
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index];
+            debug_assert!(slot.node.is_none());
+            slot.node = Some(create(index));
+            NodeAddress::from_local_generation(self.graph, index, slot.generation)
+        } else {
+            let index = self.slots.len();
+            self.slots.push(Slot {
+                generation: 0,
+                node: Some(create(index)),
+            });
+            NodeAddress::from_local(self.graph, index)
+        }
+    }
+
+    /// Gets the id of the graph owning the store.
+    pub fn graph(&self) -> GraphId {
+        self.graph
+    }
+
+    /// Gets the node at the specified address.
+    pub fn get(&self, address: &NodeAddress) -> Result<&N, NodeAddressError> {
+        match address {
+            NodeAddress::Local { graph, .. } if *graph != self.graph => {
+                Err(NodeAddressError::ForeignGraph(address.clone()))
+            }
+            NodeAddress::Local {
+                index, generation, ..
+            } => {
+                let slot = self
+                    .slots
+                    .get(*index)
+                    .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone()))?;
+                match &slot.node {
+                    Some(node) if slot.generation == *generation => Ok(node),
+                    _ => Err(NodeAddressError::NodeRemoved(address.clone())),
+                }
+            }
+        }
+    }
+
+    /// Gets the node at the specified address for mutation.
+    pub fn get_mut(&mut self, address: &NodeAddress) -> Result<&mut N, NodeAddressError> {
+        match address {
+            NodeAddress::Local { graph, .. } if *graph != self.graph => {
+                Err(NodeAddressError::ForeignGraph(address.clone()))
+            }
+            NodeAddress::Local {
+                index, generation, ..
+            } => {
+                let slot = self
+                    .slots
+                    .get_mut(*index)
+                    .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone()))?;
+                match &mut slot.node {
+                    Some(node) if slot.generation == *generation => Ok(node),
+                    _ => Err(NodeAddressError::NodeRemoved(address.clone())),
+                }
+            }
+        }
+    }
+
+    /// Removes the node at the specified address, invalidating the address.
+    pub fn remove(&mut self, address: &NodeAddress) -> Result<N, NodeAddressError> {
+        self.get(address)?;
+        match address {
+            NodeAddress::Local { index, .. } => {
+                let slot = &mut self.slots[*index];
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(*index);
+                Ok(slot.node.take().expect("slot was checked to be occupied"))
+            }
+        }
+    }
+
+    /// Gets the number of stored nodes.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Gives the store a fresh id, returning the one it had before.
+    ///
+    /// Graphs call this after deserialization, so that no two restored copies share an id,
+    /// neither with each other nor with a graph created in this process.
+    #[cfg(feature = "serde")]
+    pub fn renew_graph(&mut self) -> GraphId {
+        std::mem::replace(&mut self.graph, GraphId::next())
+    }
+
+    /// Gets the number of slots, including vacant ones.
+    #[cfg(feature = "serde")]
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Gets the address of the node in the specified slot, if the slot is occupied.
+    #[cfg(feature = "serde")]
+    pub fn address_of(&self, index: usize) -> Option<NodeAddress> {
+        let slot = self.slots.get(index)?;
+        slot.node
+            .as_ref()
+            .map(|_| NodeAddress::from_local_generation(self.graph, index, slot.generation))
+    }
+
+    /// Iterates all nodes in slot order, together with their addresses.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeAddress, &N)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let address = NodeAddress::from_local_generation(self.graph, index, slot.generation);
+            slot.node.as_ref().map(|node| (address, node))
+        })
+    }
+
+    /// Consumes the store, yielding the generation and the node (if any) of every slot in slot order.
+    pub fn into_slots(self) -> impl Iterator<Item = (u32, Option<N>)> {
+        self.slots
+            .into_iter()
+            .map(|slot| (slot.generation, slot.node))
+    }
+}
+
+impl<N> Default for NodeSlots<N> {
+    fn default() -> Self {
+        NodeSlots {
+            graph: GraphId::next(),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, N> serde::Deserialize<'de> for NodeSlots<N>
+where
+    N: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct Repr<N> {
+            graph: GraphId,
+            slots: Vec<Slot<N>>,
+            free: Vec<usize>,
+        }
+
+        // Every vacant slot must be listed exactly once in the free list.
+        let Repr { graph, slots, free } = Repr::deserialize(deserializer)?;
+        let mut listed = vec![false; slots.len()];
+        for &index in &free {
+            match slots.get(index) {
+                Some(slot) if slot.node.is_none() && !listed[index] => listed[index] = true,
+                _ => return Err(D::Error::custom(format!("invalid free slot index {index}"))),
+            }
+        }
+        if slots.iter().filter(|slot| slot.node.is_none()).count() != free.len() {
+            return Err(D::Error::custom(
+                "vacant slots are missing from the free list",
+            ));
+        }
+
+        Ok(NodeSlots { graph, slots, free })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stale_address_is_rejected() {
+        let mut slots = NodeSlots::default();
+        let first = slots.insert_with(|_| "first");
+        assert_eq!(slots.remove(&first).unwrap(), "first");
+
+        let second = slots.insert_with(|_| "second");
+        assert_eq!(
+            second,
+            NodeAddress::from_local_generation(slots.graph(), 0, 1)
+        );
+        assert!(matches!(
+            slots.get(&first),
+            Err(NodeAddressError::NodeRemoved(_))
+        ));
+        assert_eq!(*slots.get(&second).unwrap(), "second");
+    }
+
+    #[test]
+    fn unknown_address_is_rejected() {
+        let mut slots = NodeSlots::<()>::default();
+        let address = NodeAddress::from_local(slots.graph(), 0);
+        assert!(matches!(
+            slots.get(&address),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+        assert!(matches!(
+            slots.remove(&address),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn foreign_address_is_rejected() {
+        let mut a = NodeSlots::default();
+        let mut b = NodeSlots::default();
+        let address = a.insert_with(|_| "a");
+        b.insert_with(|_| "b");
+
+        // The address has a matching index and generation in both stores.
+        assert!(matches!(
+            b.get(&address),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert!(matches!(
+            b.get_mut(&address),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert!(matches!(
+            b.remove(&address),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert_eq!(*a.get(&address).unwrap(), "a");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_rejects_inconsistent_free_list() {
+        let json = r#"{"graph":0,"slots":[{"generation":0,"node":1},{"generation":1,"node":null}],"free":[]}"#;
+        assert!(serde_json::from_str::<NodeSlots<i32>>(json).is_err());
+
+        let json = r#"{"graph":0,"slots":[{"generation":0,"node":1},{"generation":1,"node":null}],"free":[0]}"#;
+        assert!(serde_json::from_str::<NodeSlots<i32>>(json).is_err());
+
+        let json = r#"{"graph":0,"slots":[{"generation":0,"node":1},{"generation":1,"node":null}],"free":[1]}"#;
+        let slots = serde_json::from_str::<NodeSlots<i32>>(json).unwrap();
+        assert_eq!(slots.len(), 1);
+    }
+}
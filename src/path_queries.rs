@@ -1,8 +1,39 @@
 //! Provides graph traversal and path queries.
+//!
+//! ## Determinism
+//!
+//! All solvers are deterministic: given the same graph, they return the same path on every run.
+//! Neighbors are visited in the order the graph yields them, which is the order in which
+//! relations were linked. Breadth-first and depth-first searches expand nodes in
+//! first-in-first-out and last-in-first-out order of their discovery, respectively.
+//!
+//! Where the A* and Dijkstra searches have several candidates with the same estimated total cost
+//! (the f-score), they prefer
+//!
+//! 1. the lower cost from the start (the g-score), then
+//! 2. the candidate that was discovered first.
+//!
+//! ## Filtering
+//!
+//! To restrict which relations may be traversed or which nodes may be visited, run a query
+//! on a [`FilteredGraph`](crate::filtered_graph::FilteredGraph) view of the graph.
+
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use std::collections::{HashMap, HashSet};
 
 pub mod astar;
+pub mod bellman_ford;
+pub(crate) mod best_first;
 pub mod bfs;
+pub mod bidirectional_astar;
+pub mod bidirectional_bfs;
 pub mod dfs;
+pub mod dijkstra;
+pub mod johnson;
+pub(crate) mod open_set;
+pub mod shortest_path_tree;
+pub mod yen;
 
 /// Trait for heuristics.
 pub trait AdmissibleHeuristic<N> {
@@ -21,3 +52,159 @@ pub trait PathCost<N, R> {
     /// If there is no path to the target node, the returned cost should be [`f32::INFINITY`].
     fn path_cost(&self, from: &N, to: &N, relation: &R) -> f32;
 }
+
+/// Trait for sets of target nodes, for searches that stop at the nearest of several targets.
+///
+/// Implemented for collections of node addresses and for predicates on node addresses.
+/// Slices, arrays and vectors are scanned linearly on every lookup; for more than a handful
+/// of targets, pass a [`HashSet`] instead.
+pub trait Targets {
+    /// Determines whether the specified node is a target.
+    fn contains(&self, address: &NodeAddress) -> bool;
+}
+
+impl Targets for [NodeAddress] {
+    fn contains(&self, address: &NodeAddress) -> bool {
+        <[NodeAddress]>::contains(self, address)
+    }
+}
+
+impl<const N: usize> Targets for [NodeAddress; N] {
+    fn contains(&self, address: &NodeAddress) -> bool {
+        self[..].contains(address)
+    }
+}
+
+impl Targets for Vec<NodeAddress> {
+    fn contains(&self, address: &NodeAddress) -> bool {
+        self[..].contains(address)
+    }
+}
+
+impl Targets for HashSet<NodeAddress> {
+    fn contains(&self, address: &NodeAddress) -> bool {
+        HashSet::contains(self, address)
+    }
+}
+
+impl<F> Targets for F
+where
+    F: Fn(&NodeAddress) -> bool,
+{
+    fn contains(&self, address: &NodeAddress) -> bool {
+        self(address)
+    }
+}
+
+/// The path between the nearest pair of several start and several target nodes.
+#[derive(Debug, Clone)]
+pub struct NearestPath<R>
+where
+    R: Clone,
+{
+    /// The start node the path begins at.
+    pub source: NodeAddress,
+    /// The target node the path ends at.
+    pub target: NodeAddress,
+    /// The cost of the path; for breadth-first searches, the number of relations.
+    pub cost: f32,
+    /// The path from `source` to `target`.
+    pub path: Vec<NodePathLink<R>>,
+}
+
+impl<R> NearestPath<R>
+where
+    R: Clone,
+{
+    /// Creates the result of a search from a non-empty path.
+    pub(crate) fn new(path: Vec<NodePathLink<R>>, cost: f32) -> Self {
+        let source = path.first().expect("path is not empty").address.clone();
+        let target = path.last().expect("path is not empty").address.clone();
+        NearestPath {
+            source,
+            target,
+            cost,
+            path,
+        }
+    }
+}
+
+/// Reconstructs the path to `current_addr` by following the predecessor links back to the start.
+fn reconstruct_path<R>(
+    came_from: &HashMap<NodeAddress, Option<NodePathLink<R>>>,
+    mut current_addr: NodeAddress,
+) -> Vec<NodePathLink<R>>
+where
+    R: Clone,
+{
+    // TODO: Track the number of steps to directly allocate a vector of the correct size?
+    let mut path = Vec::default();
+    while let Some(parent) = came_from.get(&current_addr).cloned() {
+        if let Some(relation) = parent {
+            path.push(NodePathLink {
+                address: current_addr,
+                relation: relation.relation,
+            });
+            current_addr = relation.address.clone();
+        } else {
+            path.push(NodePathLink {
+                address: current_addr,
+                relation: None,
+            });
+
+            path.reverse();
+            return path;
+        }
+    }
+
+    unreachable!()
+}
+
+/// Reconstructs the path to `current_addr` by following predecessor links back to the start.
+///
+/// Each link holds the predecessor's address and the relation leading from it, borrowed from the
+/// graph; only the relations along the resulting path are cloned.
+fn reconstruct_borrowed_path<R>(
+    came_from: &HashMap<NodeAddress, Option<(NodeAddress, &R)>>,
+    mut current_addr: NodeAddress,
+) -> Vec<NodePathLink<R>>
+where
+    R: Clone,
+{
+    let mut path = Vec::default();
+    while let Some(Some((previous, relation))) = came_from.get(&current_addr) {
+        path.push(NodePathLink {
+            address: current_addr,
+            relation: Some((*relation).clone()),
+        });
+        current_addr = previous.clone();
+    }
+    path.push(NodePathLink {
+        address: current_addr,
+        relation: None,
+    });
+
+    path.reverse();
+    path
+}
+
+/// Joins the paths found by a bidirectional search at the `meeting` node.
+///
+/// The `forward` links point from each node back towards the start,
+/// while the `backward` links point from each node onwards towards the target.
+fn join_paths<R>(
+    forward: &HashMap<NodeAddress, Option<NodePathLink<R>>>,
+    backward: &HashMap<NodeAddress, Option<NodePathLink<R>>>,
+    meeting: NodeAddress,
+) -> Vec<NodePathLink<R>>
+where
+    R: Clone,
+{
+    let mut path = reconstruct_path(forward, meeting.clone());
+    let mut current_addr = meeting;
+    while let Some(Some(next)) = backward.get(&current_addr) {
+        path.push(next.clone());
+        current_addr = next.address.clone();
+    }
+    path
+}
@@ -1,8 +1,11 @@
-use crate::embedded_property_graph::EmbeddedPropertyGraph;
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
 use crate::node_address::NodeAddress;
 use crate::node_relation::NodePathLink;
-use crate::path_queries::{AdmissibleHeuristic, PathCost};
-use std::collections::HashMap;
+use crate::path_queries::best_first::{best_first_search, SearchVisitor};
+use crate::path_queries::{reconstruct_path, AdmissibleHeuristic, NearestPath, PathCost, Targets};
+use crate::traversal::Control;
+use std::collections::{HashMap, HashSet};
 
 /// An A* search solver for shortest path queries.
 #[derive(Debug, Default)]
@@ -21,312 +24,488 @@ impl AStarSearch {
     /// ## Returns
     ///
     /// A path from `start` to `target` or an empty vector if no such path exists.
-    pub fn shortest_path<N, R, P, H>(
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn shortest_path<G, P, H>(
         &self,
-        graph: &EmbeddedPropertyGraph<N, R>,
+        graph: &G,
         start: NodeAddress,
         target: NodeAddress,
         path_cost: &P,
         heuristic: &H,
-    ) -> Vec<NodePathLink<R>>
+    ) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
     where
-        R: Clone,
-        P: PathCost<N, R>,
-        H: AdmissibleHeuristic<N>,
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+        H: AdmissibleHeuristic<G::Node>,
     {
-        // The set of nodes to be evaluated
-        let mut open_set = OpenSet::default();
-
-        // Cost from start along best known path
-        let mut g_scores: HashMap<NodeAddress, f32> = HashMap::new();
-
-        // For each node, which node it can most efficiently be reached from
-        let mut came_from: HashMap<NodeAddress, Option<NodePathLink<R>>> = HashMap::new();
-
-        // Cost from start (to start) along best known path is zero
-        g_scores.insert(start.clone(), 0.0);
-
-        // Cost from start to goal, estimated by heuristic
-        open_set.insert(
-            start.clone(),
-            heuristic.heuristic(
-                &graph
-                    .local_node_data_ref(&start)
-                    .expect("the start node does not exist in the graph"),
-                &graph
-                    .local_node_data_ref(&target)
-                    .expect("the target node does not exist in the graph"),
-            ),
-        );
-
-        // There is no path towards the start node; it just is.
-        came_from.insert(start.clone(), None);
-
-        // Fetch the node with the lowest f-score from the open set.
-        while let Some(current_addr) = open_set.pop() {
-            if current_addr == target {
-                return reconstruct_path(came_from, current_addr);
-            }
-
-            // Get the current node's g-score to avoid later lookups.
-            let current_g_score = *g_scores
-                .get(&current_addr)
-                .expect("current node has no g-score");
-
-            // Process all neighbors of the current node.
-            let current_node_data = graph.local_node_data_ref(&current_addr).unwrap();
-            let neighbors = graph.iter_local_neighbors(&current_addr).unwrap();
-            for neighbor in neighbors {
-                let neighbor_node = &graph
-                    .local_node_data_ref(&neighbor.address)
-                    .expect("the neighbor node does not exist in the graph");
-
-                // Determine actual distance between the current node and the neighbor.
-                let distance_cost =
-                    path_cost.path_cost(&current_node_data, &neighbor_node, &neighbor.relation);
-
-                // Determine the true distance to the neighbor node from the current node.
-                let tentative_g_score = current_g_score + distance_cost;
-
-                // Determine the true cost to the neighbor node if it was already visited before.
-                let mut neighbor_g_score =
-                    *g_scores.get(&neighbor.address).unwrap_or(&f32::INFINITY);
-
-                // Only update the neighbor node if we found a shorter path to it.
-                if tentative_g_score < neighbor_g_score {
-                    neighbor_g_score = tentative_g_score;
-
-                    // Insert the path to the neighbor along the current node's outgoing relation.
-                    // TODO: Insert step count here to simplify buffer creation later on?
-                    came_from.insert(
-                        neighbor.address.clone(),
-                        Some(NodePathLink {
-                            address: current_addr.clone(),
-                            relation: Some(neighbor.relation.clone()),
-                        }),
-                    );
-
-                    // Update the g-score with the better value.
-                    g_scores.insert(neighbor.address.clone(), neighbor_g_score);
-
-                    // Calculate the f-score of the neighbor with the heuristic from the neighbor
-                    // node towards the goal node.
-                    let neighbor_f_score = neighbor_g_score
-                        + heuristic.heuristic(
-                            &neighbor_node,
-                            &graph
-                                .local_node_data_ref(&target)
-                                .expect("the target node does not exist in the graph"),
-                        );
-                    debug_assert!(neighbor_f_score.is_finite());
-
-                    // Update the open set.
-                    open_set.insert(neighbor.address.clone(), neighbor_f_score);
-                }
-            }
-        }
+        graph.local_node_data_ref(&target)?;
+        Ok(self
+            .nearest_path(
+                graph,
+                [start],
+                std::slice::from_ref(&target),
+                path_cost,
+                heuristic,
+            )?
+            .map_or_else(Vec::default, |nearest| nearest.path))
+    }
 
-        // Failure; no path found.
-        Vec::default()
+    /// Performs a shortest path query on the specified graph, starting at any of the `starts`,
+    /// attempting to reach the nearest of the `targets`.
+    ///
+    /// This is equivalent to, but much faster than, a query for every pair of start and
+    /// target nodes, keeping the cheapest path. The heuristic towards the nearest target
+    /// is the smallest heuristic towards any of the targets.
+    ///
+    /// Unlike [`DijkstraSearch::nearest_path`](crate::path_queries::dijkstra::DijkstraSearch::nearest_path),
+    /// the targets cannot be selected by a predicate, since computing that heuristic requires
+    /// the data of every target up front. To search for the nearest node matching a predicate,
+    /// use the Dijkstra search instead.
+    ///
+    /// Target membership is looked up in a hash set, but the heuristic is evaluated towards
+    /// each of the `M` targets, so expanding a node costs `O(M)` heuristic evaluations.
+    /// For many targets and a cheap path cost, the Dijkstra search may be faster.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `starts` - The starting addresses in the graph.
+    /// * `targets` - The target addresses in the graph.
+    ///
+    /// ## Returns
+    ///
+    /// The cheapest path from any start to any target, along with the start and target
+    /// it connects, or `None` if no target is reachable.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if any of the `starts` or `targets` does not address a node of the graph.
+    pub fn nearest_path<G, P, H>(
+        &self,
+        graph: &G,
+        starts: impl IntoIterator<Item = NodeAddress>,
+        targets: &[NodeAddress],
+        path_cost: &P,
+        heuristic: &H,
+    ) -> Result<Option<NearestPath<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+        H: AdmissibleHeuristic<G::Node>,
+    {
+        let target_nodes = targets
+            .iter()
+            .map(|target| graph.local_node_data_ref(target))
+            .collect::<Result<Vec<_>, _>>()?;
+        let target_set: HashSet<NodeAddress> = targets.iter().cloned().collect();
+
+        // Without targets, the search exhausts the reachable nodes.
+        let estimate = |node: &G::Node| {
+            target_nodes
+                .iter()
+                .map(|target_node| heuristic.heuristic(node, target_node))
+                .min_by(f32::total_cmp)
+                .unwrap_or(0.0)
+        };
+        search(graph, starts, &target_set, path_cost, estimate)
     }
 }
 
-/// The open set of nodes; maintains a priority queue of nodes sorted by
-/// their f-score in ascending order.
-#[derive(Debug, Default)]
-struct OpenSet {
-    f_scores: HashMap<NodeAddress, f32>,
+/// Searches from all `starts` at once until the first target is expanded.
+///
+/// The `estimate` provides the heuristic value of a node towards the nearest target;
+/// without a heuristic, this is a Dijkstra search.
+pub(super) fn search<G, P, T, E>(
+    graph: &G,
+    starts: impl IntoIterator<Item = NodeAddress>,
+    targets: &T,
+    path_cost: &P,
+    estimate: E,
+) -> Result<Option<NearestPath<G::Relation>>, NodeAddressError>
+where
+    G: Graph,
+    G::Relation: Clone,
+    P: PathCost<G::Node, G::Relation>,
+    T: Targets + ?Sized,
+    E: Fn(&G::Node) -> f32,
+{
+    let mut visitor = NearestTarget {
+        targets,
+        came_from: HashMap::new(),
+        nearest: None,
+    };
+    best_first_search(
+        graph,
+        starts,
+        path_cost,
+        |_, node| estimate(node),
+        &mut visitor,
+    )?;
+    Ok(visitor.nearest)
 }
 
-impl OpenSet {
-    /// Inserts a node into the open set, overwriting any existing values.
-    pub fn insert(&mut self, address: NodeAddress, f_score: f32) {
-        self.f_scores.insert(address, f_score);
-    }
-
-    /// Returns the node with the smallest f-score from the set, if any.
-    pub fn pop(&mut self) -> Option<NodeAddress> {
-        let mut smallest_score = f32::INFINITY;
-        let mut best_node = None;
-        for (address, &score) in self.f_scores.iter() {
-            if score < smallest_score {
-                smallest_score = score;
-                best_node = Some(address.clone())
-            }
-        }
-
-        if let Some(best_node) = best_node {
-            self.f_scores.remove(&best_node);
-            Some(best_node)
-        } else {
-            None
-        }
-    }
+/// Records the best known paths until the first target is expanded.
+struct NearestTarget<'a, T, R>
+where
+    T: ?Sized,
+    R: Clone,
+{
+    targets: &'a T,
+    /// For each node, which node it can most efficiently be reached from
+    came_from: HashMap<NodeAddress, Option<NodePathLink<R>>>,
+    nearest: Option<NearestPath<R>>,
 }
 
-fn reconstruct_path<R>(
-    came_from: HashMap<NodeAddress, Option<NodePathLink<R>>>,
-    mut current_addr: NodeAddress,
-) -> Vec<NodePathLink<R>>
+impl<'a, T, R> SearchVisitor<R> for NearestTarget<'a, T, R>
 where
+    T: Targets + ?Sized,
     R: Clone,
 {
-    // TODO: Track the number of steps to directly allocate a vector of the correct size?
-    let mut path = Vec::default();
-    while let Some(parent) = came_from.get(&current_addr).cloned() {
-        if let Some(relation) = parent {
-            path.push(NodePathLink {
-                address: current_addr,
-                relation: relation.relation,
-            });
-            current_addr = relation.address.clone();
-        } else {
-            path.push(NodePathLink {
-                address: current_addr,
-                relation: None,
-            });
-
-            path.reverse();
-            return path;
+    fn start(&mut self, node: &NodeAddress) {
+        // There is no path towards the start node; it just is.
+        self.came_from.insert(node.clone(), None);
+    }
+
+    fn expand(&mut self, node: &NodeAddress, g_score: f32) -> Control {
+        if !self.targets.contains(node) {
+            return Control::Continue;
         }
+
+        let path = reconstruct_path(&self.came_from, node.clone());
+        self.nearest = Some(NearestPath::new(path, g_score));
+        Control::Break
     }
 
-    unreachable!()
+    fn improve(
+        &mut self,
+        from: &NodeAddress,
+        _edge: usize,
+        relation: &R,
+        to: &NodeAddress,
+        _g_score: f32,
+    ) {
+        // Insert the path to the neighbor along the current node's outgoing relation.
+        self.came_from.insert(
+            to.clone(),
+            Some(NodePathLink {
+                address: from.clone(),
+                relation: Some(relation.clone()),
+            }),
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
     use crate::examples::london_graph::{
-        london_graph, ConnectionType, LondonGraphDistanceCost, LondonGraphDistanceHeuristic,
-        LondonGraphStationsCost,
+        london_graph, london_property_graph, ConnectionType, LondonGraphDistanceCost,
+        LondonGraphDistanceHeuristic, LondonGraphStationsCost,
     };
+    use crate::test_support::RelationValue;
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn with_stations_cost() {
-        let solver = AStarSearch::default();
+        let solver = AStarSearch;
         let graph = london_graph();
-        let heuristic = LondonGraphDistanceHeuristic::default();
-        let path_cost = LondonGraphStationsCost::default();
-        let path = solver.shortest_path(
-            &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(198),
-            &path_cost,
-            &heuristic,
-        );
+        let heuristic = LondonGraphDistanceHeuristic;
+        let path_cost = LondonGraphStationsCost;
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+                &path_cost,
+                &heuristic,
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 6);
-        assert_eq!(path[0].address, NodeAddress::Local(0));
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 0));
 
-        assert_eq!(path[1].address, NodeAddress::Local(45));
+        assert_eq!(path[1].address, NodeAddress::from_local(graph.id(), 45));
         assert_eq!(path[1].relation, Some(ConnectionType::Bus));
 
-        assert_eq!(path[2].address, NodeAddress::Local(12));
+        assert_eq!(path[2].address, NodeAddress::from_local(graph.id(), 12));
         assert_eq!(path[2].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[3].address, NodeAddress::Local(88));
+        assert_eq!(path[3].address, NodeAddress::from_local(graph.id(), 88));
         assert_eq!(path[3].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[4].address, NodeAddress::Local(127));
+        assert_eq!(path[4].address, NodeAddress::from_local(graph.id(), 127));
         assert_eq!(path[4].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[5].address, NodeAddress::Local(198));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 198));
         assert_eq!(path[5].relation, Some(ConnectionType::Bus));
     }
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn with_distance_cost() {
-        let solver = AStarSearch::default();
+        let solver = AStarSearch;
         let graph = london_graph();
-        let heuristic = LondonGraphDistanceHeuristic::default();
-        let path_cost = LondonGraphDistanceCost::default();
-        let path = solver.shortest_path(
-            &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(198),
-            &path_cost,
-            &heuristic,
-        );
+        let heuristic = LondonGraphDistanceHeuristic;
+        let path_cost = LondonGraphDistanceCost;
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+                &path_cost,
+                &heuristic,
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 10);
-        assert_eq!(path[0].address, NodeAddress::Local(0));
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 0));
 
-        assert_eq!(path[1].address, NodeAddress::Local(45));
+        assert_eq!(path[1].address, NodeAddress::from_local(graph.id(), 45));
         assert_eq!(path[1].relation, Some(ConnectionType::Bus));
 
-        assert_eq!(path[2].address, NodeAddress::Local(78));
+        assert_eq!(path[2].address, NodeAddress::from_local(graph.id(), 78));
         assert_eq!(path[2].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[3].address, NodeAddress::Local(110));
+        assert_eq!(path[3].address, NodeAddress::from_local(graph.id(), 110));
         assert_eq!(path[3].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[4].address, NodeAddress::Local(152));
+        assert_eq!(path[4].address, NodeAddress::from_local(graph.id(), 152));
         assert_eq!(path[4].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[5].address, NodeAddress::Local(183));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 183));
         assert_eq!(path[5].relation, Some(ConnectionType::Bus));
 
-        assert_eq!(path[6].address, NodeAddress::Local(184));
+        assert_eq!(path[6].address, NodeAddress::from_local(graph.id(), 184));
         assert_eq!(path[6].relation, Some(ConnectionType::Taxi));
 
-        assert_eq!(path[7].address, NodeAddress::Local(186));
+        assert_eq!(path[7].address, NodeAddress::from_local(graph.id(), 186));
         assert_eq!(path[7].relation, Some(ConnectionType::Bus));
 
-        assert_eq!(path[8].address, NodeAddress::Local(187));
+        assert_eq!(path[8].address, NodeAddress::from_local(graph.id(), 187));
         assert_eq!(path[8].relation, Some(ConnectionType::Taxi));
 
-        assert_eq!(path[9].address, NodeAddress::Local(198));
+        assert_eq!(path[9].address, NodeAddress::from_local(graph.id(), 198));
         assert_eq!(path[9].relation, Some(ConnectionType::Taxi));
     }
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn unreachable() {
-        let solver = AStarSearch::default();
+        let solver = AStarSearch;
         let graph = london_graph();
-        let heuristic = LondonGraphDistanceHeuristic::default();
-        let path_cost = LondonGraphDistanceCost::default();
-        let path = solver.shortest_path(
-            &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(199),
-            &path_cost,
-            &heuristic,
-        );
+        let heuristic = LondonGraphDistanceHeuristic;
+        let path_cost = LondonGraphDistanceCost;
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+                &path_cost,
+                &heuristic,
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 0);
     }
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn ferries() {
-        let solver = AStarSearch::default();
+        let solver = AStarSearch;
         let graph = london_graph();
-        let heuristic = LondonGraphDistanceHeuristic::default();
-        let path_cost = LondonGraphStationsCost::default();
-        let path = solver.shortest_path(
-            &graph,
-            NodeAddress::from_local(191),
-            NodeAddress::from_local(118),
-            &path_cost,
-            &heuristic,
-        );
+        let heuristic = LondonGraphDistanceHeuristic;
+        let path_cost = LondonGraphStationsCost;
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 191),
+                NodeAddress::from_local(graph.id(), 118),
+                &path_cost,
+                &heuristic,
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 6);
-        assert_eq!(path[0].address, NodeAddress::Local(191));
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 191));
 
-        assert_eq!(path[1].address, NodeAddress::Local(193));
+        assert_eq!(path[1].address, NodeAddress::from_local(graph.id(), 193));
         assert_eq!(path[1].relation, Some(ConnectionType::Taxi));
 
-        assert_eq!(path[2].address, NodeAddress::Local(156));
+        assert_eq!(path[2].address, NodeAddress::from_local(graph.id(), 156));
         assert_eq!(path[2].relation, Some(ConnectionType::Ferry));
 
-        assert_eq!(path[3].address, NodeAddress::Local(114));
+        assert_eq!(path[3].address, NodeAddress::from_local(graph.id(), 114));
         assert_eq!(path[3].relation, Some(ConnectionType::Ferry));
 
-        assert_eq!(path[4].address, NodeAddress::Local(107));
+        assert_eq!(path[4].address, NodeAddress::from_local(graph.id(), 107));
         assert_eq!(path[4].relation, Some(ConnectionType::Ferry));
 
-        assert_eq!(path[5].address, NodeAddress::Local(118));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 118));
         assert_eq!(path[5].relation, Some(ConnectionType::Taxi));
     }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_path_on_property_graph() {
+        let solver = AStarSearch;
+        let heuristic = LondonGraphDistanceHeuristic;
+        let path_cost = LondonGraphDistanceCost;
+        let embedded_graph = london_graph();
+        let embedded = solver
+            .shortest_path(
+                &embedded_graph,
+                NodeAddress::from_local(embedded_graph.id(), 0),
+                NodeAddress::from_local(embedded_graph.id(), 198),
+                &path_cost,
+                &heuristic,
+            )
+            .unwrap();
+        let property_graph = london_property_graph();
+        let property = solver
+            .shortest_path(
+                &property_graph,
+                NodeAddress::from_local(property_graph.id(), 0),
+                NodeAddress::from_local(property_graph.id(), 198),
+                &path_cost,
+                &heuristic,
+            )
+            .unwrap();
+
+        // Each graph has its own id, so the paths are compared by slot index.
+        let slot = |address: &NodeAddress| match address {
+            NodeAddress::Local { index, .. } => *index,
+        };
+        assert_eq!(embedded.len(), property.len());
+        for (a, b) in embedded.iter().zip(property.iter()) {
+            assert_eq!(slot(&a.address), slot(&b.address));
+            assert_eq!(a.relation, b.relation);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = AStarSearch.shortest_path(
+            &graph,
+            NodeAddress::from_local(graph.id(), 1000),
+            NodeAddress::from_local(graph.id(), 0),
+            &LondonGraphStationsCost,
+            &LondonGraphDistanceHeuristic,
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
+
+    /// Uses the value stored in the node as the heuristic.
+    struct StoredHeuristic;
+
+    impl AdmissibleHeuristic<(&str, f32)> for StoredHeuristic {
+        fn heuristic(&self, from: &(&str, f32), _to: &(&str, f32)) -> f32 {
+            from.1
+        }
+    }
+
+    #[test]
+    fn reopens_closed_nodes() {
+        // The heuristic is admissible but not consistent, so C is first expanded
+        // along the more expensive path through A and must be reopened.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let s = graph.add(("S", 0.0));
+        let a = graph.add(("A", 0.0));
+        let b = graph.add(("B", 4.0));
+        let c = graph.add(("C", 0.0));
+        let g = graph.add(("G", 0.0));
+        graph.link_to(&s, &a, 1.0).unwrap();
+        graph.link_to(&s, &b, 2.0).unwrap();
+        graph.link_to(&a, &c, 3.0).unwrap();
+        graph.link_to(&b, &c, 1.0).unwrap();
+        graph.link_to(&c, &g, 3.0).unwrap();
+
+        let path = AStarSearch
+            .shortest_path(
+                &graph,
+                s.clone(),
+                g.clone(),
+                &RelationValue,
+                &StoredHeuristic,
+            )
+            .unwrap();
+        let addresses: Vec<_> = path.into_iter().map(|link| link.address).collect();
+        assert_eq!(addresses, [s, b, c, g]);
+    }
+
+    #[test]
+    fn ties_prefer_first_discovered() {
+        for reversed in [false, true] {
+            let mut graph = EmbeddedPropertyGraph::default();
+            let s = graph.add(("S", 0.0));
+            let a = graph.add(("A", 0.0));
+            let b = graph.add(("B", 0.0));
+            let g = graph.add(("G", 0.0));
+            let (first, second) = if reversed { (&b, &a) } else { (&a, &b) };
+            graph.link_to(&s, first, 1.0).unwrap();
+            graph.link_to(&s, second, 1.0).unwrap();
+            graph.link_to(&a, &g, 1.0).unwrap();
+            graph.link_to(&b, &g, 1.0).unwrap();
+
+            let path = AStarSearch
+                .shortest_path(
+                    &graph,
+                    s.clone(),
+                    g.clone(),
+                    &RelationValue,
+                    &StoredHeuristic,
+                )
+                .unwrap();
+            assert_eq!(path[1].address, *first);
+        }
+    }
+
+    #[test]
+    fn nearest_path() {
+        // Two starts and two targets; s2 -> t1 is the cheapest pair.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let s1 = graph.add(("S1", 0.0));
+        let s2 = graph.add(("S2", 0.0));
+        let m = graph.add(("M", 0.0));
+        let t1 = graph.add(("T1", 0.0));
+        let t2 = graph.add(("T2", 0.0));
+        graph.link_to(&s1, &m, 2.0).unwrap();
+        graph.link_to(&s2, &m, 1.0).unwrap();
+        graph.link_to(&m, &t1, 2.0).unwrap();
+        graph.link_to(&m, &t2, 3.0).unwrap();
+        graph.link_to(&s1, &t2, 4.0).unwrap();
+
+        let nearest = AStarSearch
+            .nearest_path(
+                &graph,
+                [s1.clone(), s2.clone()],
+                &[t1.clone(), t2.clone()],
+                &RelationValue,
+                &StoredHeuristic,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(nearest.source, s2);
+        assert_eq!(nearest.target, t1);
+        assert_eq!(nearest.cost, 3.0);
+        let addresses: Vec<_> = nearest.path.into_iter().map(|link| link.address).collect();
+        assert_eq!(addresses, [s2, m, t1]);
+
+        let result = AStarSearch.nearest_path(
+            &graph,
+            [s1],
+            &[NodeAddress::from_local(graph.id(), 1000)],
+            &RelationValue,
+            &StoredHeuristic,
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
 }
@@ -0,0 +1,369 @@
+use crate::errors::{NodeAddressError, ShortestPathError};
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::path_queries::shortest_path_tree::ShortestPathTree;
+use crate::path_queries::PathCost;
+use std::collections::{HashMap, HashSet};
+
+/// A Bellman-Ford search solver for weighted shortest path queries with negative path costs.
+///
+/// Unlike the [`DijkstraSearch`](crate::path_queries::dijkstra::DijkstraSearch), relations may
+/// have negative costs, at the expense of a running time proportional to the number of nodes
+/// times the number of relations. Nodes on or behind a cycle of negative total cost have
+/// no shortest path; such cycles are reported as errors.
+#[derive(Debug, Default)]
+pub struct BellmanFordSearch;
+
+impl BellmanFordSearch {
+    /// Performs a shortest path query on the specified graph,
+    /// starting at the `start` node, attempting to reach the `target` node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `target` - The target address in the graph.
+    /// * `path_cost` - The cost of following a relation; may be negative.
+    ///
+    /// ## Returns
+    ///
+    /// A path from `start` to `target` or an empty vector if no such path exists.
+    ///
+    /// ## Errors
+    ///
+    /// Fails with [`ShortestPathError::NegativeCycle`] if a cycle of negative total cost is
+    /// reachable from `start`, or if `start` or `target` does not address a node of the graph.
+    pub fn shortest_path<G, P>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        target: NodeAddress,
+        path_cost: &P,
+    ) -> Result<Vec<NodePathLink<G::Relation>>, ShortestPathError<G::Relation>>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        graph.local_node_data_ref(&target)?;
+        Ok(self
+            .shortest_path_tree(graph, start, path_cost)?
+            .path_to(&target))
+    }
+
+    /// Determines the shortest paths from the `start` node to every reachable node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `path_cost` - The cost of following a relation; may be negative.
+    ///
+    /// ## Errors
+    ///
+    /// Fails with [`ShortestPathError::NegativeCycle`] if a cycle of negative total cost is
+    /// reachable from `start`, or if `start` does not address a node of the graph.
+    /// The cycle is a path that starts and ends at the same node.
+    pub fn shortest_path_tree<G, P>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        path_cost: &P,
+    ) -> Result<ShortestPathTree<G::Relation>, ShortestPathError<G::Relation>>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        graph.local_node_data_ref(&start)?;
+
+        let relations = weighted_relations(graph, path_cost)?;
+        let mut relaxation = Relaxation::new([start.clone()]);
+        relaxation.run(graph.node_count(), &relations)?;
+        Ok(relaxation.into_tree(start))
+    }
+}
+
+/// A relation of the graph along with its cost.
+pub(crate) struct WeightedRelation<'a, R> {
+    pub from: NodeAddress,
+    pub relation: &'a R,
+    pub to: NodeAddress,
+    pub cost: f32,
+}
+
+/// Determines the cost of every relation of the graph, omitting relations of infinite cost.
+pub(crate) fn weighted_relations<'a, G, P>(
+    graph: &'a G,
+    path_cost: &P,
+) -> Result<Vec<WeightedRelation<'a, G::Relation>>, NodeAddressError>
+where
+    G: Graph,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let mut relations = Vec::with_capacity(graph.edge_count());
+    for (from, relation, to) in graph.edges() {
+        let cost = path_cost.path_cost(
+            graph.local_node_data_ref(&from)?,
+            graph.local_node_data_ref(&to)?,
+            relation,
+        );
+        if cost < f32::INFINITY {
+            relations.push(WeightedRelation {
+                from,
+                relation,
+                to,
+                cost,
+            });
+        }
+    }
+    Ok(relations)
+}
+
+/// The distances and predecessors found by repeatedly relaxing all relations.
+pub(crate) struct Relaxation<'a, R> {
+    /// Cost from the closest source along the shortest path.
+    pub distances: HashMap<NodeAddress, f32>,
+    /// For each node, which node it is reached from; `None` for the sources.
+    came_from: HashMap<NodeAddress, Option<(NodeAddress, &'a R)>>,
+}
+
+impl<'a, R> Relaxation<'a, R>
+where
+    R: Clone,
+{
+    /// Starts out with the specified source nodes, each at a distance of zero.
+    pub fn new(sources: impl IntoIterator<Item = NodeAddress>) -> Self {
+        let mut relaxation = Relaxation {
+            distances: HashMap::new(),
+            came_from: HashMap::new(),
+        };
+        for source in sources {
+            relaxation.distances.insert(source.clone(), 0.0);
+            relaxation.came_from.insert(source, None);
+        }
+        relaxation
+    }
+
+    /// Relaxes all relations until no distance improves any further.
+    ///
+    /// Every shortest path consists of fewer relations than there are nodes, so if distances
+    /// still improve in pass `node_count`, a cycle of negative cost is reachable.
+    pub fn run(
+        &mut self,
+        node_count: usize,
+        relations: &[WeightedRelation<'a, R>],
+    ) -> Result<(), ShortestPathError<R>> {
+        let mut last_improved = None;
+        for _ in 0..node_count {
+            last_improved = None;
+            for relation in relations {
+                let Some(&from_distance) = self.distances.get(&relation.from) else {
+                    continue;
+                };
+
+                let tentative = from_distance + relation.cost;
+                let distance = self
+                    .distances
+                    .get(&relation.to)
+                    .copied()
+                    .unwrap_or(f32::INFINITY);
+                if tentative < distance {
+                    self.distances.insert(relation.to.clone(), tentative);
+                    self.came_from.insert(
+                        relation.to.clone(),
+                        Some((relation.from.clone(), relation.relation)),
+                    );
+                    last_improved = Some(relation.to.clone());
+                }
+            }
+
+            if last_improved.is_none() {
+                return Ok(());
+            }
+        }
+
+        match last_improved {
+            Some(address) => Err(ShortestPathError::NegativeCycle(self.cycle_before(address))),
+            None => Ok(()),
+        }
+    }
+
+    /// Gets the predecessor of a node that was reached from another node.
+    fn predecessor(&self, address: &NodeAddress) -> &(NodeAddress, &'a R) {
+        self.came_from[address]
+            .as_ref()
+            .expect("nodes improved in the last pass are preceded by a negative cycle")
+    }
+
+    /// Extracts the negative cycle that precedes a node improved in the last pass.
+    fn cycle_before(&self, address: NodeAddress) -> Vec<NodePathLink<R>> {
+        // Walk back until a node repeats; that node lies on the cycle.
+        let mut seen = HashSet::new();
+        let mut current_addr = address;
+        while seen.insert(current_addr.clone()) {
+            current_addr = self.predecessor(&current_addr).0.clone();
+        }
+
+        let start = current_addr.clone();
+        let mut cycle = Vec::new();
+        loop {
+            let (previous, relation) = self.predecessor(&current_addr);
+            cycle.push(NodePathLink {
+                address: current_addr,
+                relation: Some((*relation).clone()),
+            });
+            current_addr = previous.clone();
+            if current_addr == start {
+                break;
+            }
+        }
+        cycle.push(NodePathLink {
+            address: start,
+            relation: None,
+        });
+
+        cycle.reverse();
+        cycle
+    }
+
+    /// Converts the result of a relaxation from a single source into a shortest path tree.
+    pub fn into_tree(self, source: NodeAddress) -> ShortestPathTree<R> {
+        let mut tree = ShortestPathTree::new(source);
+        for (address, link) in self.came_from {
+            if let Some((previous, relation)) = link {
+                let distance = self.distances[&address];
+                tree.update(
+                    address,
+                    distance,
+                    NodePathLink {
+                        address: previous,
+                        relation: Some(relation.clone()),
+                    },
+                );
+            }
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, LondonGraphStationsCost};
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::dijkstra::DijkstraSearch;
+    use crate::test_support::RelationValue;
+
+    /// Builds the example graph from "Introduction to Algorithms", figure 24.4.
+    fn rebate_graph() -> (EmbeddedPropertyGraph<&'static str, f32>, [NodeAddress; 5]) {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [s, t, x, y, z] = ["s", "t", "x", "y", "z"].map(|name| graph.add(name));
+        for (from, to, cost) in [
+            (&s, &t, 6.0),
+            (&s, &y, 7.0),
+            (&t, &x, 5.0),
+            (&t, &y, 8.0),
+            (&t, &z, -4.0),
+            (&x, &t, -2.0),
+            (&y, &x, -3.0),
+            (&y, &z, 9.0),
+            (&z, &s, 2.0),
+            (&z, &x, 7.0),
+        ] {
+            graph.link_to(from, to, cost).unwrap();
+        }
+        (graph, [s, t, x, y, z])
+    }
+
+    #[test]
+    fn it_works() {
+        let (graph, [s, t, x, y, z]) = rebate_graph();
+        let tree = BellmanFordSearch
+            .shortest_path_tree(&graph, s.clone(), &RelationValue)
+            .unwrap();
+        assert_eq!(tree.distance(&t), Some(2.0));
+        assert_eq!(tree.distance(&x), Some(4.0));
+        assert_eq!(tree.distance(&y), Some(7.0));
+        assert_eq!(tree.distance(&z), Some(-2.0));
+
+        let path = BellmanFordSearch
+            .shortest_path(&graph, s.clone(), z.clone(), &RelationValue)
+            .unwrap();
+        let addresses: Vec<_> = path.iter().map(|link| link.address.clone()).collect();
+        assert_eq!(addresses, [s, y, x, t, z]);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // The cycle b -> c -> b costs -1 in total; d can only be reached from it.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, 1.0).unwrap();
+        graph.link_to(&b, &c, -2.0).unwrap();
+        graph.link_to(&c, &b, 1.0).unwrap();
+        graph.link_to(&c, &d, 1.0).unwrap();
+
+        let result = BellmanFordSearch.shortest_path(&graph, a.clone(), d.clone(), &RelationValue);
+        let Err(ShortestPathError::NegativeCycle(cycle)) = result else {
+            panic!("expected a negative cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(
+            cycle.first().unwrap().address,
+            cycle.last().unwrap().address
+        );
+        let cost: f32 = cycle.iter().filter_map(|link| link.relation).sum();
+        assert_eq!(cost, -1.0);
+
+        // The cycle is not reachable from d.
+        let tree = BellmanFordSearch
+            .shortest_path_tree(&graph, d.clone(), &RelationValue)
+            .unwrap();
+        assert_eq!(tree.len(), 1);
+
+        // A node related to itself at a negative cost forms a cycle on its own.
+        graph.link_to(&d, &d, -1.0).unwrap();
+        let result = BellmanFordSearch.shortest_path_tree(&graph, d.clone(), &RelationValue);
+        let Err(ShortestPathError::NegativeCycle(cycle)) = result else {
+            panic!("expected a negative cycle");
+        };
+        let addresses: Vec<_> = cycle.into_iter().map(|link| link.address).collect();
+        assert_eq!(addresses, [d.clone(), d]);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_as_dijkstra() {
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 0);
+        let tree = BellmanFordSearch
+            .shortest_path_tree(&graph, start.clone(), &LondonGraphStationsCost)
+            .unwrap();
+        let distances = DijkstraSearch
+            .distances(&graph, start, &LondonGraphStationsCost)
+            .unwrap();
+        assert_eq!(tree.distances(), &distances);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = BellmanFordSearch.shortest_path(
+            &graph,
+            NodeAddress::from_local(graph.id(), 0),
+            NodeAddress::from_local(graph.id(), 1000),
+            &LondonGraphStationsCost,
+        );
+        assert!(matches!(
+            result,
+            Err(ShortestPathError::InvalidNode(
+                NodeAddressError::NodeNotFound(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,149 @@
+//! Provides the best-first search shared by the cost-based solvers.
+
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::path_queries::open_set::OpenSet;
+use crate::path_queries::PathCost;
+use crate::traversal::Control;
+use std::collections::HashMap;
+
+/// Receives the events of a [`best_first_search`].
+pub(crate) trait SearchVisitor<R> {
+    /// Called for every distinct start node before the first node is expanded.
+    fn start(&mut self, _node: &NodeAddress) {}
+
+    /// Called when a node is expanded, along with the cost of the best path found to it.
+    ///
+    /// Returning [`Control::Prune`] skips the relations of the node,
+    /// returning [`Control::Break`] stops the search.
+    fn expand(&mut self, _node: &NodeAddress, _g_score: f32) -> Control {
+        Control::Continue
+    }
+
+    /// Called before following the relation at position `edge` among the outgoing relations
+    /// of `from` to the `to` node; returning `false` ignores the relation.
+    fn follow(&mut self, _from: &NodeAddress, _edge: usize, _to: &NodeAddress) -> bool {
+        true
+    }
+
+    /// Called when a cheaper path to the `to` node was found, leading from `from` along
+    /// `relation`, the relation at position `edge` among the outgoing relations of `from`.
+    fn improve(
+        &mut self,
+        _from: &NodeAddress,
+        _edge: usize,
+        _relation: &R,
+        _to: &NodeAddress,
+        _g_score: f32,
+    ) {
+    }
+
+    /// Called when a path to the `to` node, leading along `relation` from `from`, costs as much
+    /// as the best path found to it so far.
+    ///
+    /// The `to` node may already have been expanded if the relation costs nothing.
+    fn tie(&mut self, _from: &NodeAddress, _relation: &R, _to: &NodeAddress) {}
+}
+
+/// Expands nodes in order of their cost from the nearest of the `starts` plus their `estimate`,
+/// until the visitor stops the search or all reachable nodes were expanded.
+///
+/// With an estimate of zero, this is a Dijkstra search; otherwise, it is an A* search with the
+/// estimate as its heuristic. Expanded nodes are reopened if a cheaper path to them is found,
+/// which only happens for inconsistent heuristics.
+///
+/// ## Errors
+///
+/// Fails if any of the `starts` does not address a node of the graph,
+/// or if a reachable relation leads to a node that does not exist.
+pub(crate) fn best_first_search<G, P, E, V>(
+    graph: &G,
+    starts: impl IntoIterator<Item = NodeAddress>,
+    path_cost: &P,
+    estimate: E,
+    visitor: &mut V,
+) -> Result<(), NodeAddressError>
+where
+    G: Graph,
+    P: PathCost<G::Node, G::Relation>,
+    E: Fn(&NodeAddress, &G::Node) -> f32,
+    V: SearchVisitor<G::Relation>,
+{
+    // The set of nodes to be evaluated
+    let mut open_set = OpenSet::default();
+
+    // Cost from the nearest start along best known path
+    let mut g_scores: HashMap<NodeAddress, f32> = HashMap::new();
+
+    for start in starts {
+        let start_node = graph.local_node_data_ref(&start)?;
+        if g_scores.contains_key(&start) {
+            continue;
+        }
+
+        // Cost from start (to start) along best known path is zero
+        g_scores.insert(start.clone(), 0.0);
+
+        // Cost from start to goal, estimated by heuristic
+        open_set.insert(start.clone(), estimate(&start, start_node), 0.0);
+        visitor.start(&start);
+    }
+
+    // Fetch the node with the lowest f-score from the open set.
+    while let Some(current_addr) = open_set.pop() {
+        let current_g_score = g_scores[&current_addr];
+        match visitor.expand(&current_addr, current_g_score) {
+            Control::Continue => {}
+            Control::Prune => continue,
+            Control::Break => break,
+        }
+
+        // Process all neighbors of the current node.
+        let current_node_data = graph.local_node_data_ref(&current_addr)?;
+        for (edge, neighbor) in graph.iter_local_neighbors(&current_addr)?.enumerate() {
+            if !visitor.follow(&current_addr, edge, neighbor.address) {
+                continue;
+            }
+
+            let neighbor_node = graph.local_node_data_ref(neighbor.address)?;
+
+            // Determine the true distance to the neighbor node from the current node.
+            let tentative_g_score = current_g_score
+                + path_cost.path_cost(current_node_data, neighbor_node, neighbor.relation);
+
+            // Determine the true cost to the neighbor node if it was already visited before.
+            let neighbor_g_score = g_scores
+                .get(neighbor.address)
+                .copied()
+                .unwrap_or(f32::INFINITY);
+
+            // Only update the neighbor node if we found a shorter path to it.
+            if tentative_g_score < neighbor_g_score {
+                g_scores.insert(neighbor.address.clone(), tentative_g_score);
+                visitor.improve(
+                    &current_addr,
+                    edge,
+                    neighbor.relation,
+                    neighbor.address,
+                    tentative_g_score,
+                );
+
+                // Calculate the f-score of the neighbor with the heuristic from the neighbor
+                // node towards the goal node.
+                let neighbor_f_score =
+                    tentative_g_score + estimate(neighbor.address, neighbor_node);
+                debug_assert!(neighbor_f_score.is_finite());
+                open_set.insert(
+                    neighbor.address.clone(),
+                    neighbor_f_score,
+                    tentative_g_score,
+                );
+            } else if tentative_g_score == neighbor_g_score && neighbor_g_score < f32::INFINITY {
+                visitor.tie(&current_addr, neighbor.relation, neighbor.address);
+            }
+        }
+    }
+
+    Ok(())
+}
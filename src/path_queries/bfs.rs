@@ -1,7 +1,9 @@
-use crate::embedded_property_graph::EmbeddedPropertyGraph;
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
 use crate::node_address::NodeAddress;
 use crate::node_relation::NodePathLink;
-use std::collections::{HashSet, VecDeque};
+use crate::path_queries::{reconstruct_borrowed_path, NearestPath, Targets};
+use std::collections::{HashMap, VecDeque};
 
 /// A breadth-first search (BFS) solver for shortest path queries.
 #[derive(Debug, Default)]
@@ -20,95 +22,255 @@ impl BreadthFirstSearch {
     /// ## Returns
     ///
     /// A path from `start` to `target` or an empty vector if no such path exists.
-    pub fn shortest_path<N, R>(
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn shortest_path<G>(
         &self,
-        graph: &EmbeddedPropertyGraph<N, R>,
+        graph: &G,
         start: NodeAddress,
         target: NodeAddress,
-    ) -> Vec<NodePathLink<R>>
+    ) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
     where
-        R: Clone,
+        G: Graph,
+        G::Relation: Clone,
     {
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back(vec![NodePathLink {
-            relation: None,
-            address: start,
-        }]);
+        graph.local_node_data_ref(&start)?;
+        graph.local_node_data_ref(&target)?;
+        Ok(self
+            .nearest_path(graph, [start], std::slice::from_ref(&target))?
+            .map_or_else(Vec::default, |nearest| nearest.path))
+    }
 
-        while let Some(path) = queue.pop_front() {
-            let current_node = path.last().expect("path must not be empty").clone();
+    /// Performs a shortest path query on the specified graph, starting at any of the `starts`,
+    /// attempting to reach the nearest of the `targets`.
+    ///
+    /// This is equivalent to, but much faster than, a query for every pair of start and
+    /// target nodes, keeping the shortest path.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `starts` - The starting addresses in the graph.
+    /// * `targets` - The target addresses, or a predicate selecting them.
+    ///
+    /// ## Returns
+    ///
+    /// The path with the fewest relations from any start to any target, along with
+    /// the start and target it connects, or `None` if no target is reachable.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if any of the `starts` does not address a node of the graph.
+    pub fn nearest_path<G, T>(
+        &self,
+        graph: &G,
+        starts: impl IntoIterator<Item = NodeAddress>,
+        targets: &T,
+    ) -> Result<Option<NearestPath<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        T: Targets + ?Sized,
+    {
+        // For each discovered node, the node it was discovered from and the relation followed.
+        let mut came_from = HashMap::new();
+        let mut queue = VecDeque::new();
 
-            if current_node.address == target {
-                return path;
+        for start in starts {
+            graph.local_node_data_ref(&start)?;
+            if !came_from.contains_key(&start) {
+                came_from.insert(start.clone(), None);
+                queue.push_back(start);
             }
+        }
 
-            if visited.contains(&current_node.address) {
-                continue;
+        while let Some(current_addr) = queue.pop_front() {
+            if targets.contains(&current_addr) {
+                let path = reconstruct_borrowed_path(&came_from, current_addr);
+                let cost = (path.len() - 1) as f32;
+                return Ok(Some(NearestPath::new(path, cost)));
             }
 
-            visited.insert(current_node.address.clone());
-
-            let neighbors = graph
-                .iter_local_neighbors(&current_node.address)
-                .expect("remote node lookups are not yet supported");
-
+            let neighbors = graph.iter_local_neighbors(&current_addr)?;
             for relation in neighbors {
-                let mut new_path = path.clone();
-                new_path.push(relation.into());
-                queue.push_back(new_path);
+                if !came_from.contains_key(relation.address) {
+                    came_from.insert(
+                        relation.address.clone(),
+                        Some((current_addr.clone(), relation.relation)),
+                    );
+                    queue.push_back(relation.address.clone());
+                }
             }
         }
 
-        Vec::default()
+        Ok(None)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::examples::london_graph::{london_graph, ConnectionType};
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, london_property_graph, ConnectionType};
+    use std::cell::Cell;
+    use std::rc::Rc;
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn it_works() {
-        let solver = BreadthFirstSearch::default();
+        let solver = BreadthFirstSearch;
         let graph = london_graph();
-        let path = solver.shortest_path(
-            &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(198),
-        );
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 6);
-        assert_eq!(path[0].address, NodeAddress::Local(0));
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 0));
 
-        assert_eq!(path[1].address, NodeAddress::Local(45));
+        assert_eq!(path[1].address, NodeAddress::from_local(graph.id(), 45));
         assert_eq!(path[1].relation, Some(ConnectionType::Bus));
 
-        assert_eq!(path[2].address, NodeAddress::Local(12));
+        assert_eq!(path[2].address, NodeAddress::from_local(graph.id(), 12));
         assert_eq!(path[2].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[3].address, NodeAddress::Local(88));
+        assert_eq!(path[3].address, NodeAddress::from_local(graph.id(), 88));
         assert_eq!(path[3].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[4].address, NodeAddress::Local(127));
+        assert_eq!(path[4].address, NodeAddress::from_local(graph.id(), 127));
         assert_eq!(path[4].relation, Some(ConnectionType::Underground));
 
-        assert_eq!(path[5].address, NodeAddress::Local(198));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 198));
         assert_eq!(path[5].relation, Some(ConnectionType::Bus));
     }
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn unreachable() {
-        let solver = BreadthFirstSearch::default();
+        let solver = BreadthFirstSearch;
+        let graph = london_graph();
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn nearest_path() {
+        let graph = london_graph();
+        let starts = [0, 150].map(|index| NodeAddress::from_local(graph.id(), index));
+        let targets = [127, 198].map(|index| NodeAddress::from_local(graph.id(), index));
+        let nearest = BreadthFirstSearch
+            .nearest_path(&graph, starts.clone(), &targets)
+            .unwrap()
+            .unwrap();
+        assert_eq!(nearest.cost as usize, nearest.path.len() - 1);
+
+        // The same as querying every pair of start and target.
+        let fewest = starts
+            .iter()
+            .flat_map(|start| {
+                targets.iter().map(|target| {
+                    BreadthFirstSearch
+                        .shortest_path(&graph, start.clone(), target.clone())
+                        .unwrap()
+                        .len()
+                })
+            })
+            .min()
+            .unwrap();
+        assert_eq!(nearest.path.len(), fewest);
+
+        // Targets may also be selected by a predicate; a start may be a target itself.
+        let is_island =
+            |address: &NodeAddress| *address == NodeAddress::from_local(graph.id(), 199);
+        let nearest = BreadthFirstSearch
+            .nearest_path(&graph, starts.clone(), &is_island)
+            .unwrap();
+        assert!(nearest.is_none());
+        let nearest = BreadthFirstSearch
+            .nearest_path(&graph, starts.clone(), &|address: &NodeAddress| {
+                starts.contains(address)
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (nearest.source, nearest.target),
+            (starts[0].clone(), starts[0].clone())
+        );
+        assert_eq!(nearest.cost, 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn property_graph() {
+        let solver = BreadthFirstSearch;
+        let graph = london_property_graph();
+        let path = solver
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 0));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 198));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
         let graph = london_graph();
-        let path = solver.shortest_path(
+        let result = BreadthFirstSearch.shortest_path(
             &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(199),
+            NodeAddress::from_local(graph.id(), 0),
+            NodeAddress::from_local(graph.id(), 1000),
         );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
 
-        assert_eq!(path.len(), 0);
+    /// A relation that counts how often it was cloned.
+    #[derive(Debug)]
+    struct CountedRelation(Rc<Cell<usize>>);
+
+    impl Clone for CountedRelation {
+        fn clone(&self) -> Self {
+            self.0.set(self.0.get() + 1);
+            CountedRelation(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn clones_only_relations_on_path() {
+        let clones = Rc::new(Cell::new(0));
+        let mut graph = EmbeddedPropertyGraph::default();
+        let nodes: Vec<_> = (0..20).map(|index| graph.add(index)).collect();
+        for from in &nodes {
+            for to in &nodes {
+                graph
+                    .link_to(from, to, CountedRelation(clones.clone()))
+                    .unwrap();
+            }
+        }
+
+        let path = BreadthFirstSearch
+            .shortest_path(&graph, nodes[0].clone(), nodes[19].clone())
+            .unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(clones.get(), 1);
     }
 }
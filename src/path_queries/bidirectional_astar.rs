@@ -0,0 +1,318 @@
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::path_queries::open_set::OpenSet;
+use crate::path_queries::{join_paths, AdmissibleHeuristic, PathCost};
+use std::collections::HashMap;
+
+/// A bidirectional A* search solver for shortest path queries.
+///
+/// Searches forward from the start towards the target and backward from the target
+/// towards the start, following incoming relations, until no cheaper path than the best
+/// one found where both searches met can exist.
+///
+/// The backward search estimates the remaining cost from a node `n` as
+/// `heuristic(start, n)`, so the heuristic must be admissible in both directions.
+#[derive(Debug, Default)]
+pub struct BidirectionalAStarSearch;
+
+impl BidirectionalAStarSearch {
+    /// Performs a shortest path query on the specified graph,
+    /// starting at the `start` node, attempting to reach the `target` node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `target` - The target address in the graph.
+    /// * `path_cost` - The cost of following a relation.
+    /// * `heuristic` - The estimated cost between two nodes.
+    ///
+    /// ## Returns
+    ///
+    /// A path from `start` to `target` or an empty vector if no such path exists.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn shortest_path<G, P, H>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        target: NodeAddress,
+        path_cost: &P,
+        heuristic: &H,
+    ) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+        H: AdmissibleHeuristic<G::Node>,
+    {
+        let start_node = graph.local_node_data_ref(&start)?;
+        let target_node = graph.local_node_data_ref(&target)?;
+
+        let mut forward = Side::new(
+            start.clone(),
+            Direction::Outgoing,
+            heuristic.heuristic(start_node, target_node),
+        );
+        let mut backward = Side::new(
+            target.clone(),
+            Direction::Incoming,
+            heuristic.heuristic(start_node, target_node),
+        );
+
+        // The cost of the best path found so far and the node at which its halves meet.
+        let mut best = (f32::INFINITY, start.clone());
+        if start == target {
+            best.0 = 0.0;
+        }
+
+        let mut forward_turn = true;
+        // Each f-score is a lower bound for the cost of any path through the open nodes
+        // of its side, so no cheaper path exists once either of them reaches the best cost.
+        while let (Some(forward_f), Some(backward_f)) = (
+            forward.open_set.peek_f_score(),
+            backward.open_set.peek_f_score(),
+        ) {
+            if forward_f.max(backward_f) >= best.0 {
+                break;
+            }
+
+            if forward_turn {
+                forward.expand(graph, &backward, &mut best, |node, relation, neighbor| {
+                    let cost = path_cost.path_cost(node, neighbor, relation);
+                    (cost, heuristic.heuristic(neighbor, target_node))
+                })?;
+            } else {
+                backward.expand(graph, &forward, &mut best, |node, relation, neighbor| {
+                    let cost = path_cost.path_cost(neighbor, node, relation);
+                    (cost, heuristic.heuristic(start_node, neighbor))
+                })?;
+            }
+            forward_turn = !forward_turn;
+        }
+
+        let (cost, meeting) = best;
+        if cost.is_finite() {
+            Ok(join_paths(&forward.came_from, &backward.came_from, meeting))
+        } else {
+            Ok(Vec::default())
+        }
+    }
+}
+
+/// One half of the bidirectional search.
+struct Side<R>
+where
+    R: Clone,
+{
+    direction: Direction,
+    open_set: OpenSet,
+    /// Cost from the origin of this side along best known path
+    g_scores: HashMap<NodeAddress, f32>,
+    /// For each node, which node it can most efficiently be reached from
+    came_from: HashMap<NodeAddress, Option<NodePathLink<R>>>,
+}
+
+impl<R> Side<R>
+where
+    R: Clone,
+{
+    fn new(origin: NodeAddress, direction: Direction, f_score: f32) -> Self {
+        let mut open_set = OpenSet::default();
+        let mut g_scores = HashMap::new();
+        let mut came_from = HashMap::new();
+        g_scores.insert(origin.clone(), 0.0);
+        came_from.insert(origin.clone(), None);
+        open_set.insert(origin, f_score, 0.0);
+        Side {
+            direction,
+            open_set,
+            g_scores,
+            came_from,
+        }
+    }
+
+    /// Expands the open node with the lowest f-score, updating the `best` path
+    /// wherever a neighbor was already reached by the `other` side.
+    ///
+    /// The `score` function determines the cost of following a relation from the expanded node
+    /// to a neighbor and the heuristic estimate for the neighbor.
+    fn expand<G, F>(
+        &mut self,
+        graph: &G,
+        other: &Side<R>,
+        best: &mut (f32, NodeAddress),
+        score: F,
+    ) -> Result<(), NodeAddressError>
+    where
+        G: Graph<Relation = R>,
+        F: Fn(&G::Node, &R, &G::Node) -> (f32, f32),
+    {
+        let Some(current_addr) = self.open_set.pop() else {
+            return Ok(());
+        };
+
+        let current_g_score = self.g_scores[&current_addr];
+        let current_node_data = graph.local_node_data_ref(&current_addr)?;
+        for neighbor in graph.iter_local_neighbors_directed(&current_addr, self.direction)? {
+            let neighbor_node = graph.local_node_data_ref(neighbor.address)?;
+            let (distance_cost, estimate) =
+                score(current_node_data, neighbor.relation, neighbor_node);
+            let tentative_g_score = current_g_score + distance_cost;
+
+            let neighbor_g_score = *self
+                .g_scores
+                .get(neighbor.address)
+                .unwrap_or(&f32::INFINITY);
+            if tentative_g_score >= neighbor_g_score {
+                continue;
+            }
+
+            self.came_from.insert(
+                neighbor.address.clone(),
+                Some(NodePathLink {
+                    address: current_addr.clone(),
+                    relation: Some(neighbor.relation.clone()),
+                }),
+            );
+            self.g_scores
+                .insert(neighbor.address.clone(), tentative_g_score);
+            self.open_set.insert(
+                neighbor.address.clone(),
+                tentative_g_score + estimate,
+                tentative_g_score,
+            );
+
+            if let Some(other_g_score) = other.g_scores.get(neighbor.address) {
+                let cost = tentative_g_score + other_g_score;
+                if cost < best.0 {
+                    *best = (cost, neighbor.address.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "examples-london")]
+    use super::*;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{
+        london_graph, LondonGraphDistanceHeuristic, LondonGraphStationsCost,
+    };
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bfs::BreadthFirstSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::test_support::ZeroHeuristic;
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_length_as_bfs() {
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 0);
+        for target in [1, 12, 127, 198] {
+            let target = NodeAddress::from_local(graph.id(), target);
+            let expected = BreadthFirstSearch
+                .shortest_path(&graph, start.clone(), target.clone())
+                .unwrap();
+            let path = BidirectionalAStarSearch
+                .shortest_path(
+                    &graph,
+                    start.clone(),
+                    target.clone(),
+                    &LondonGraphStationsCost,
+                    &ZeroHeuristic,
+                )
+                .unwrap();
+
+            assert_eq!(path.len(), expected.len());
+            assert_eq!(path[0].address, start);
+            assert!(path[0].relation.is_none());
+            assert_eq!(path[path.len() - 1].address, target);
+            for step in path.windows(2) {
+                let relation = step[1].relation.as_ref().unwrap();
+                assert!(graph
+                    .iter_local_neighbors(&step[0].address)
+                    .unwrap()
+                    .any(|neighbor| neighbor.address == step[1].address
+                        && &neighbor.relation == relation));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn with_distance_heuristic() {
+        let graph = london_graph();
+        let path = BidirectionalAStarSearch
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 191),
+                NodeAddress::from_local(graph.id(), 118),
+                &LondonGraphStationsCost,
+                &LondonGraphDistanceHeuristic,
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 191));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 118));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unreachable() {
+        let graph = london_graph();
+        let path = BidirectionalAStarSearch
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+                &LondonGraphStationsCost,
+                &ZeroHeuristic,
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_start_and_target() {
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 5);
+        let path = BidirectionalAStarSearch
+            .shortest_path(
+                &graph,
+                start.clone(),
+                start.clone(),
+                &LondonGraphStationsCost,
+                &ZeroHeuristic,
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 1);
+        assert_eq!(path[0].address, start);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = BidirectionalAStarSearch.shortest_path(
+            &graph,
+            NodeAddress::from_local(graph.id(), 1000),
+            NodeAddress::from_local(graph.id(), 0),
+            &LondonGraphStationsCost,
+            &ZeroHeuristic,
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::path_queries::join_paths;
+use std::collections::HashMap;
+
+/// A bidirectional breadth-first search solver for shortest path queries.
+///
+/// Searches forward from the start and backward from the target, following incoming
+/// relations, until both searches meet. Compared to the
+/// [`BreadthFirstSearch`](crate::path_queries::bfs::BreadthFirstSearch), far fewer nodes
+/// are expanded on long point-to-point queries.
+#[derive(Debug, Default)]
+pub struct BidirectionalBreadthFirstSearch;
+
+impl BidirectionalBreadthFirstSearch {
+    /// Performs a shortest path query on the specified graph,
+    /// starting at the `start` node, attempting to reach the `target` node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `target` - The target address in the graph.
+    ///
+    /// ## Returns
+    ///
+    /// A path from `start` to `target` or an empty vector if no such path exists.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn shortest_path<G>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        target: NodeAddress,
+    ) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+    {
+        graph.local_node_data_ref(&start)?;
+        graph.local_node_data_ref(&target)?;
+
+        if start == target {
+            return Ok(vec![NodePathLink {
+                relation: None,
+                address: start,
+            }]);
+        }
+
+        let mut forward = Side::new(start, Direction::Outgoing);
+        let mut backward = Side::new(target, Direction::Incoming);
+
+        while !forward.frontier.is_empty() && !backward.frontier.is_empty() {
+            // Always grow the smaller frontier by one full level.
+            let meeting = if forward.frontier.len() <= backward.frontier.len() {
+                forward.expand_level(graph, &backward)?
+            } else {
+                backward.expand_level(graph, &forward)?
+            };
+
+            if let Some(meeting) = meeting {
+                return Ok(join_paths(&forward.came_from, &backward.came_from, meeting));
+            }
+        }
+
+        Ok(Vec::default())
+    }
+}
+
+/// One half of the bidirectional search.
+struct Side<R>
+where
+    R: Clone,
+{
+    direction: Direction,
+    /// The nodes discovered in the last level.
+    frontier: Vec<NodeAddress>,
+    /// For each discovered node, the node it was discovered from.
+    came_from: HashMap<NodeAddress, Option<NodePathLink<R>>>,
+}
+
+impl<R> Side<R>
+where
+    R: Clone,
+{
+    fn new(origin: NodeAddress, direction: Direction) -> Self {
+        let mut came_from = HashMap::new();
+        came_from.insert(origin.clone(), None);
+        Side {
+            direction,
+            frontier: vec![origin],
+            came_from,
+        }
+    }
+
+    /// Expands the nodes of the frontier, returning the node at which both sides meet, if any.
+    ///
+    /// Since the sides did not meet in any earlier level, the first meeting node
+    /// lies on a shortest path.
+    fn expand_level<G>(
+        &mut self,
+        graph: &G,
+        other: &Side<R>,
+    ) -> Result<Option<NodeAddress>, NodeAddressError>
+    where
+        G: Graph<Relation = R>,
+    {
+        for current_addr in std::mem::take(&mut self.frontier) {
+            for neighbor in graph.iter_local_neighbors_directed(&current_addr, self.direction)? {
+                if self.came_from.contains_key(neighbor.address) {
+                    continue;
+                }
+
+                self.came_from.insert(
+                    neighbor.address.clone(),
+                    Some(NodePathLink {
+                        address: current_addr.clone(),
+                        relation: Some(neighbor.relation.clone()),
+                    }),
+                );
+                if other.came_from.contains_key(neighbor.address) {
+                    return Ok(Some(neighbor.address.clone()));
+                }
+                self.frontier.push(neighbor.address.clone());
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, london_property_graph};
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bfs::BreadthFirstSearch;
+
+    /// Asserts that each link of the path follows an existing relation.
+    #[cfg(feature = "examples-london")]
+    fn assert_valid_path<G>(graph: &G, path: &[NodePathLink<G::Relation>])
+    where
+        G: Graph,
+        G::Relation: Clone + PartialEq,
+    {
+        for step in path.windows(2) {
+            let relation = step[1].relation.as_ref().unwrap();
+            assert!(graph
+                .iter_local_neighbors(&step[0].address)
+                .unwrap()
+                .any(|neighbor| neighbor.address == &step[1].address
+                    && neighbor.relation == relation));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_length_as_bfs() {
+        for graph in [london_graph().freeze(), london_property_graph().freeze()] {
+            let start = NodeAddress::from_local(graph.id(), 0);
+            for target in [1, 12, 127, 198] {
+                let target = NodeAddress::from_local(graph.id(), target);
+                let expected = BreadthFirstSearch
+                    .shortest_path(&graph, start.clone(), target.clone())
+                    .unwrap();
+                let path = BidirectionalBreadthFirstSearch
+                    .shortest_path(&graph, start.clone(), target.clone())
+                    .unwrap();
+
+                assert_eq!(path.len(), expected.len());
+                assert_eq!(path[0].address, start);
+                assert!(path[0].relation.is_none());
+                assert_eq!(path[path.len() - 1].address, target);
+                assert_valid_path(&graph, &path);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unreachable() {
+        let graph = london_graph();
+        let path = BidirectionalBreadthFirstSearch
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 0);
+    }
+
+    #[test]
+    fn follows_relation_direction() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        let c = graph.add("c");
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&c, &b, "cb").unwrap();
+
+        let solver = BidirectionalBreadthFirstSearch;
+        let path = solver.shortest_path(&graph, a.clone(), b.clone()).unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[1].relation, Some("ab"));
+        assert!(solver
+            .shortest_path(&graph, a.clone(), c)
+            .unwrap()
+            .is_empty());
+
+        let path = solver.shortest_path(&graph, a.clone(), a).unwrap();
+        assert_eq!(path.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = BidirectionalBreadthFirstSearch.shortest_path(
+            &graph,
+            NodeAddress::from_local(graph.id(), 0),
+            NodeAddress::from_local(graph.id(), 1000),
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
+}
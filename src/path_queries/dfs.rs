@@ -1,7 +1,9 @@
-use crate::embedded_property_graph::EmbeddedPropertyGraph;
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
 use crate::node_address::NodeAddress;
 use crate::node_relation::NodePathLink;
-use std::collections::HashSet;
+use crate::path_queries::reconstruct_borrowed_path;
+use std::collections::HashMap;
 
 /// A depth-first search (DFS) solver for finding a path in a graph.
 #[derive(Debug, Default)]
@@ -19,79 +21,118 @@ impl DepthFirstSearch {
     /// ## Returns
     ///
     /// A path from `start` to `target` or an empty vector if no such path exists.
-    pub fn find_path<N, R>(
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn find_path<G>(
         &self,
-        graph: &EmbeddedPropertyGraph<N, R>,
+        graph: &G,
         start: NodeAddress,
         target: NodeAddress,
-    ) -> Vec<NodePathLink<R>>
+    ) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
     where
-        R: Clone,
+        G: Graph,
+        G::Relation: Clone,
     {
-        let mut visited = HashSet::new();
-        let mut stack = Vec::new();
+        graph.local_node_data_ref(&start)?;
+        graph.local_node_data_ref(&target)?;
 
-        stack.push(vec![NodePathLink {
-            relation: None,
-            address: start,
-        }]);
-
-        while let Some(path) = stack.pop() {
-            let current_node = path.last().expect("path must not be empty").clone();
+        // For each visited node, the node it was reached from and the relation followed.
+        let mut came_from = HashMap::new();
+        let mut stack = Vec::new();
 
-            if current_node.address == target {
-                return path;
-            }
+        stack.push((start, None));
 
-            if visited.contains(&current_node.address) {
+        while let Some((current_addr, link)) = stack.pop() {
+            if came_from.contains_key(&current_addr) {
                 continue;
             }
 
-            visited.insert(current_node.address.clone());
+            came_from.insert(current_addr.clone(), link);
 
-            let neighbors = graph
-                .iter_local_neighbors(&current_node.address)
-                .expect("remote node lookups are not yet supported");
+            if current_addr == target {
+                return Ok(reconstruct_borrowed_path(&came_from, current_addr));
+            }
+
+            let neighbors = graph.iter_local_neighbors(&current_addr)?;
 
             for relation in neighbors {
-                let mut new_path = path.clone();
-                new_path.push(relation.into());
-                stack.push(new_path);
+                stack.push((
+                    relation.address.clone(),
+                    Some((current_addr.clone(), relation.relation)),
+                ));
             }
         }
 
-        Vec::default()
+        Ok(Vec::default())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "examples-london")]
     use super::*;
-    use crate::examples::london_graph::london_graph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, london_property_graph};
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn it_works() {
-        let solver = DepthFirstSearch::default();
+        let solver = DepthFirstSearch;
         let graph = london_graph();
-        let path = solver.find_path(
-            &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(198),
-        );
+        let path = solver
+            .find_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 45);
     }
 
     #[test]
+    #[cfg(feature = "examples-london")]
     fn unreachable() {
-        let solver = DepthFirstSearch::default();
+        let solver = DepthFirstSearch;
         let graph = london_graph();
-        let path = solver.find_path(
-            &graph,
-            NodeAddress::from_local(0),
-            NodeAddress::from_local(199),
-        );
+        let path = solver
+            .find_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+            )
+            .unwrap();
 
         assert_eq!(path.len(), 0);
     }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn property_graph() {
+        let solver = DepthFirstSearch;
+        let graph = london_property_graph();
+        let path = solver
+            .find_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 45);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = DepthFirstSearch.find_path(
+            &graph,
+            NodeAddress::from_local(graph.id(), 1000),
+            NodeAddress::from_local(graph.id(), 0),
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
 }
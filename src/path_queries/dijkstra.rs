@@ -0,0 +1,447 @@
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::path_queries::best_first::{best_first_search, SearchVisitor};
+use crate::path_queries::shortest_path_tree::ShortestPathTree;
+use crate::path_queries::{astar, NearestPath, PathCost, Targets};
+use crate::traversal::Control;
+use std::collections::HashMap;
+
+/// A Dijkstra search solver for weighted shortest path queries.
+///
+/// Unlike the [`AStarSearch`](crate::path_queries::astar::AStarSearch), no heuristic is required,
+/// which makes the solver applicable to graphs without a spatial embedding.
+/// Path costs must not be negative; use the
+/// [`BellmanFordSearch`](crate::path_queries::bellman_ford::BellmanFordSearch) otherwise.
+#[derive(Debug, Default)]
+pub struct DijkstraSearch;
+
+impl DijkstraSearch {
+    /// Performs a shortest path query on the specified graph,
+    /// starting at the `start` node, attempting to reach the `target` node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `target` - The target address in the graph.
+    /// * `path_cost` - The cost of following a relation.
+    ///
+    /// ## Returns
+    ///
+    /// A path from `start` to `target` or an empty vector if no such path exists.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn shortest_path<G, P>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        target: NodeAddress,
+        path_cost: &P,
+    ) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        graph.local_node_data_ref(&target)?;
+
+        Ok(search(graph, start, Some(&target), path_cost, |_, _| 0.0)?.path_to(&target))
+    }
+
+    /// Performs a shortest path query on the specified graph, starting at any of the `starts`,
+    /// attempting to reach the nearest of the `targets`.
+    ///
+    /// This is equivalent to, but much faster than, a query for every pair of start and
+    /// target nodes, keeping the cheapest path.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `starts` - The starting addresses in the graph.
+    /// * `targets` - The target addresses, or a predicate selecting them.
+    /// * `path_cost` - The cost of following a relation.
+    ///
+    /// ## Returns
+    ///
+    /// The cheapest path from any start to any target, along with the start and target
+    /// it connects, or `None` if no target is reachable.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if any of the `starts` does not address a node of the graph.
+    pub fn nearest_path<G, P, T>(
+        &self,
+        graph: &G,
+        starts: impl IntoIterator<Item = NodeAddress>,
+        targets: &T,
+        path_cost: &P,
+    ) -> Result<Option<NearestPath<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+        T: Targets + ?Sized,
+    {
+        astar::search(graph, starts, targets, path_cost, |_| 0.0)
+    }
+
+    /// Determines the cost of the shortest path from the `start` node to every reachable node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `path_cost` - The cost of following a relation.
+    ///
+    /// ## Returns
+    ///
+    /// The path cost by node address. Nodes that cannot be reached are not contained;
+    /// the `start` node itself is reached at a cost of zero.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` does not address a node of the graph.
+    pub fn distances<G, P>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        path_cost: &P,
+    ) -> Result<HashMap<NodeAddress, f32>, NodeAddressError>
+    where
+        G: Graph,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        let mut visitor = DistanceRecorder::default();
+        best_first_search(graph, [start], path_cost, |_, _| 0.0, &mut visitor)?;
+        Ok(visitor.distances)
+    }
+
+    /// Determines the shortest paths from the `start` node to every reachable node.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `path_cost` - The cost of following a relation.
+    ///
+    /// ## Returns
+    ///
+    /// The tree of shortest paths rooted in `start`.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` does not address a node of the graph.
+    pub fn shortest_path_tree<G, P>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        path_cost: &P,
+    ) -> Result<ShortestPathTree<G::Relation>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        search(graph, start, None, path_cost, |_, _| 0.0)
+    }
+}
+
+/// Expands nodes in order of their cost from `start` plus their `estimate`, until the `target`
+/// node (if any) is reached or all reachable nodes were expanded.
+///
+/// Without an estimate, this is a Dijkstra search; the estimate allows searching on relations
+/// that were reweighted by a potential, as done by the
+/// [`JohnsonSearch`](crate::path_queries::johnson::JohnsonSearch).
+pub(super) fn search<G, P, E>(
+    graph: &G,
+    start: NodeAddress,
+    target: Option<&NodeAddress>,
+    path_cost: &P,
+    estimate: E,
+) -> Result<ShortestPathTree<G::Relation>, NodeAddressError>
+where
+    G: Graph,
+    G::Relation: Clone,
+    P: PathCost<G::Node, G::Relation>,
+    E: Fn(&NodeAddress, &G::Node) -> f32,
+{
+    let mut visitor = TreeBuilder {
+        tree: ShortestPathTree::new(start.clone()),
+        target,
+    };
+    best_first_search(graph, [start], path_cost, estimate, &mut visitor)?;
+    Ok(visitor.tree)
+}
+
+/// Records the cost of the shortest path to every reached node.
+#[derive(Default)]
+struct DistanceRecorder {
+    distances: HashMap<NodeAddress, f32>,
+}
+
+impl<R> SearchVisitor<R> for DistanceRecorder {
+    fn start(&mut self, node: &NodeAddress) {
+        self.distances.insert(node.clone(), 0.0);
+    }
+
+    fn improve(
+        &mut self,
+        _from: &NodeAddress,
+        _edge: usize,
+        _relation: &R,
+        to: &NodeAddress,
+        g_score: f32,
+    ) {
+        self.distances.insert(to.clone(), g_score);
+    }
+}
+
+/// Records the shortest path tree until the `target` node (if any) is expanded.
+struct TreeBuilder<'a, R>
+where
+    R: Clone,
+{
+    tree: ShortestPathTree<R>,
+    target: Option<&'a NodeAddress>,
+}
+
+impl<'a, R> SearchVisitor<R> for TreeBuilder<'a, R>
+where
+    R: Clone,
+{
+    fn expand(&mut self, node: &NodeAddress, _g_score: f32) -> Control {
+        if Some(node) == self.target {
+            Control::Break
+        } else {
+            Control::Continue
+        }
+    }
+
+    fn improve(
+        &mut self,
+        from: &NodeAddress,
+        _edge: usize,
+        relation: &R,
+        to: &NodeAddress,
+        g_score: f32,
+    ) {
+        self.tree.update(
+            to.clone(),
+            g_score,
+            NodePathLink {
+                address: from.clone(),
+                relation: Some(relation.clone()),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "examples-london", feature = "examples-movies"))]
+    use super::*;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{
+        london_graph, LondonGraphDistanceCost, LondonGraphDistanceHeuristic,
+        LondonGraphStationsCost,
+    };
+    #[cfg(feature = "examples-movies")]
+    use crate::examples::movie_graph::{movie_graph, NodeType, RelationType};
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::astar::AStarSearch;
+    #[cfg(feature = "examples-london")]
+    use std::collections::HashSet;
+
+    /// Sums up the cost of following a path.
+    #[cfg(feature = "examples-london")]
+    fn cost_of<G, P>(graph: &G, path: &[NodePathLink<G::Relation>], path_cost: &P) -> f32
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        path.windows(2)
+            .map(|step| {
+                let from = graph.local_node_data_ref(&step[0].address).unwrap();
+                let to = graph.local_node_data_ref(&step[1].address).unwrap();
+                path_cost.path_cost(from, to, step[1].relation.as_ref().unwrap())
+            })
+            .sum()
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn with_stations_cost() {
+        let graph = london_graph();
+        let path = DijkstraSearch
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 198),
+                &LondonGraphStationsCost,
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 6);
+        assert_eq!(path[0].address, NodeAddress::from_local(graph.id(), 0));
+        assert_eq!(path[5].address, NodeAddress::from_local(graph.id(), 198));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn not_worse_than_astar() {
+        // The distance heuristic does not bound the squared distance cost,
+        // so A* is not guaranteed to find the cheapest path here.
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 0);
+        let target = NodeAddress::from_local(graph.id(), 198);
+        let astar = AStarSearch
+            .shortest_path(
+                &graph,
+                start.clone(),
+                target.clone(),
+                &LondonGraphDistanceCost,
+                &LondonGraphDistanceHeuristic,
+            )
+            .unwrap();
+        let path = DijkstraSearch
+            .shortest_path(
+                &graph,
+                start.clone(),
+                target.clone(),
+                &LondonGraphDistanceCost,
+            )
+            .unwrap();
+
+        let cost = cost_of(&graph, &path, &LondonGraphDistanceCost);
+        assert!(cost <= cost_of(&graph, &astar, &LondonGraphDistanceCost));
+
+        let distances = DijkstraSearch
+            .distances(&graph, start, &LondonGraphDistanceCost)
+            .unwrap();
+        assert_eq!(distances[&target], cost);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unreachable() {
+        let graph = london_graph();
+        let path = DijkstraSearch
+            .shortest_path(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+                &LondonGraphStationsCost,
+            )
+            .unwrap();
+
+        assert_eq!(path.len(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn distances() {
+        let graph = london_graph();
+        let distances = DijkstraSearch
+            .distances(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                &LondonGraphStationsCost,
+            )
+            .unwrap();
+
+        assert_eq!(distances.len(), 198);
+        assert_eq!(distances[&NodeAddress::from_local(graph.id(), 0)], 0.0);
+        assert_eq!(distances[&NodeAddress::from_local(graph.id(), 198)], 5.0);
+        assert!(!distances.contains_key(&NodeAddress::from_local(graph.id(), 199)));
+    }
+
+    /// Prefers crew relations over acting credits.
+    #[cfg(feature = "examples-movies")]
+    struct CreditCost;
+
+    #[cfg(feature = "examples-movies")]
+    impl PathCost<NodeType, RelationType> for CreditCost {
+        fn path_cost(&self, _from: &NodeType, _to: &NodeType, relation: &RelationType) -> f32 {
+            match relation {
+                RelationType::ActedIn(_) => 2.0,
+                _ => 1.0,
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-movies")]
+    fn without_heuristic() {
+        let graph = movie_graph();
+        let keanu = NodeAddress::from_local(graph.id(), 1);
+        let matrix = NodeAddress::from_local(graph.id(), 0);
+        let distances = DijkstraSearch
+            .distances(&graph, keanu.clone(), &CreditCost)
+            .unwrap();
+        assert_eq!(distances[&matrix], 2.0);
+
+        let path = DijkstraSearch
+            .shortest_path(&graph, keanu.clone(), matrix.clone(), &CreditCost)
+            .unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[0].address, keanu);
+        assert_eq!(path[1].address, matrix);
+        assert!(matches!(path[1].relation, Some(RelationType::ActedIn(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn nearest_detective() {
+        // The detectives and the positions Mr X may have moved to.
+        let graph = london_graph();
+        let detectives = [12, 45, 150].map(|index| NodeAddress::from_local(graph.id(), index));
+        let positions: HashSet<_> = [100, 127, 198]
+            .map(|index| NodeAddress::from_local(graph.id(), index))
+            .into();
+
+        let nearest = DijkstraSearch
+            .nearest_path(
+                &graph,
+                detectives.clone(),
+                &positions,
+                &LondonGraphDistanceCost,
+            )
+            .unwrap()
+            .unwrap();
+        assert!(detectives.contains(&nearest.source));
+        assert!(positions.contains(&nearest.target));
+        assert_eq!(nearest.path[0].address, nearest.source);
+        assert_eq!(nearest.path.last().unwrap().address, nearest.target);
+        let path_cost = cost_of(&graph, &nearest.path, &LondonGraphDistanceCost);
+        assert!((path_cost - nearest.cost).abs() < 1e-6);
+
+        // The same as querying every pair of detective and position.
+        let mut cheapest = f32::INFINITY;
+        for detective in &detectives {
+            let distances = DijkstraSearch
+                .distances(&graph, detective.clone(), &LondonGraphDistanceCost)
+                .unwrap();
+            for position in &positions {
+                cheapest = cheapest.min(distances[position]);
+            }
+        }
+        assert_eq!(nearest.cost, cheapest);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = DijkstraSearch.distances(
+            &graph,
+            NodeAddress::from_local(graph.id(), 1000),
+            &LondonGraphStationsCost,
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
+}
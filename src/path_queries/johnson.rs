@@ -0,0 +1,149 @@
+use crate::errors::ShortestPathError;
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::path_queries::bellman_ford::{weighted_relations, Relaxation};
+use crate::path_queries::dijkstra::search;
+use crate::path_queries::shortest_path_tree::ShortestPathTree;
+use crate::path_queries::PathCost;
+use std::collections::HashMap;
+
+/// The shortest path trees of all nodes of a graph, keyed by their source node.
+pub type AllPairsShortestPaths<R> = HashMap<NodeAddress, ShortestPathTree<R>>;
+
+/// A solver for the shortest paths between all pairs of nodes using Johnson's algorithm.
+///
+/// Relations may have negative costs. A single Bellman-Ford pass determines a potential for
+/// every node, which is used to reweight the relations such that no cost is negative;
+/// a Dijkstra search from every node then finds the shortest paths. On sparse graphs,
+/// this is considerably faster than a
+/// [`BellmanFordSearch`](crate::path_queries::bellman_ford::BellmanFordSearch) from every node.
+#[derive(Debug, Default)]
+pub struct JohnsonSearch;
+
+impl JohnsonSearch {
+    /// Determines the shortest paths between all pairs of nodes of the specified graph.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `path_cost` - The cost of following a relation; may be negative.
+    ///
+    /// ## Returns
+    ///
+    /// The shortest path tree of every node of the graph, keyed by its source node.
+    /// Distances are in terms of the original, not the reweighted, path costs.
+    ///
+    /// ## Errors
+    ///
+    /// Fails with [`ShortestPathError::NegativeCycle`] if the graph contains a cycle of negative
+    /// total cost anywhere, or if the graph contains relations to nodes that do not exist.
+    pub fn all_pairs<G, P>(
+        &self,
+        graph: &G,
+        path_cost: &P,
+    ) -> Result<AllPairsShortestPaths<G::Relation>, ShortestPathError<G::Relation>>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+    {
+        let relations = weighted_relations(graph, path_cost)?;
+
+        // Starting out from all nodes at once is equivalent to adding a virtual source
+        // that is related to every node at no cost.
+        let mut relaxation = Relaxation::new(graph.nodes().map(|(address, _)| address));
+        relaxation.run(graph.node_count(), &relations)?;
+        let potential = relaxation.distances;
+
+        // Ordering the search by the cost minus the potential of the reached node is equivalent
+        // to a Dijkstra search on relations reweighted by the potentials, none of which costs
+        // less than zero; the tree records the original costs.
+        graph
+            .nodes()
+            .map(|(source, _)| {
+                let tree = search(graph, source.clone(), None, path_cost, |address, _| {
+                    -potential[address]
+                })?;
+                Ok((source, tree))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, LondonGraphStationsCost};
+    use crate::path_queries::bellman_ford::BellmanFordSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::dijkstra::DijkstraSearch;
+    use crate::test_support::RelationValue;
+
+    #[test]
+    fn it_works() {
+        // A triangle with a rebate on b -> c.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c] = ["a", "b", "c"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, 3.0).unwrap();
+        graph.link_to(&a, &c, 2.0).unwrap();
+        graph.link_to(&b, &c, -2.0).unwrap();
+        graph.link_to(&c, &a, 4.0).unwrap();
+
+        let all_pairs = JohnsonSearch.all_pairs(&graph, &RelationValue).unwrap();
+        assert_eq!(all_pairs.len(), 3);
+        assert_eq!(all_pairs[&a].distance(&c), Some(1.0));
+        assert_eq!(all_pairs[&b].distance(&a), Some(2.0));
+        assert_eq!(all_pairs[&c].distance(&b), Some(7.0));
+
+        for (source, tree) in &all_pairs {
+            let expected = BellmanFordSearch
+                .shortest_path_tree(&graph, source.clone(), &RelationValue)
+                .unwrap();
+            assert_eq!(tree.distances(), expected.distances());
+        }
+
+        let path: Vec<_> = all_pairs[&a]
+            .path_to(&c)
+            .into_iter()
+            .map(|link| link.address)
+            .collect();
+        assert_eq!(path, [a, b, c]);
+    }
+
+    #[test]
+    fn unreachable_negative_cycle() {
+        // The cycle c <-> d costs -1 in total and cannot be reached from a or b.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, 1.0).unwrap();
+        graph.link_to(&c, &d, -2.0).unwrap();
+        graph.link_to(&d, &c, 1.0).unwrap();
+
+        assert!(BellmanFordSearch
+            .shortest_path_tree(&graph, a, &RelationValue)
+            .is_ok());
+        let result = JohnsonSearch.all_pairs(&graph, &RelationValue);
+        let Err(ShortestPathError::NegativeCycle(cycle)) = result else {
+            panic!("expected a negative cycle");
+        };
+        assert_eq!(cycle.len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn same_as_dijkstra() {
+        let graph = london_graph();
+        let all_pairs = JohnsonSearch
+            .all_pairs(&graph, &LondonGraphStationsCost)
+            .unwrap();
+        assert_eq!(all_pairs.len(), graph.node_count());
+        for start in [0, 12, 198].map(|index| NodeAddress::from_local(graph.id(), index)) {
+            let distances = DijkstraSearch
+                .distances(&graph, start.clone(), &LondonGraphStationsCost)
+                .unwrap();
+            assert_eq!(all_pairs[&start].distances(), &distances);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Provides the priority queue shared by the cost-based solvers.
+
+use crate::node_address::NodeAddress;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// The open set of nodes; maintains a priority queue of nodes sorted by
+/// their f-score in ascending order.
+///
+/// The queue is a binary heap that is never updated in place: inserting a node again
+/// with a better score pushes another entry, and outdated entries are skipped
+/// when popped (lazy deletion) by consulting the set of already expanded (closed) nodes.
+///
+/// Ties in the f-score are broken by the lower g-score first, then by insertion order.
+/// Since neighbors are inserted in the order the graph yields them, this makes
+/// the expansion order and thus the resulting path deterministic.
+#[derive(Debug, Default)]
+pub(crate) struct OpenSet {
+    heap: BinaryHeap<OpenSetEntry>,
+    closed: HashSet<NodeAddress>,
+    /// The number of insertions so far, used as a tie breaker.
+    sequence: u64,
+}
+
+impl OpenSet {
+    /// Inserts a node into the open set, reopening it if it was expanded before.
+    pub fn insert(&mut self, address: NodeAddress, f_score: f32, g_score: f32) {
+        self.closed.remove(&address);
+        self.heap.push(OpenSetEntry {
+            f_score,
+            g_score,
+            sequence: self.sequence,
+            address,
+        });
+        self.sequence += 1;
+    }
+
+    /// Returns the node with the smallest f-score from the set, if any, and closes it.
+    pub fn pop(&mut self) -> Option<NodeAddress> {
+        while let Some(entry) = self.heap.pop() {
+            if self.closed.insert(entry.address.clone()) {
+                return Some(entry.address);
+            }
+        }
+        None
+    }
+
+    /// Gets the smallest f-score in the set, if any, without removing the node.
+    pub fn peek_f_score(&mut self) -> Option<f32> {
+        while let Some(entry) = self.heap.peek() {
+            if !self.closed.contains(&entry.address) {
+                return Some(entry.f_score);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+}
+
+/// An entry in the [`OpenSet`] heap.
+#[derive(Debug)]
+struct OpenSetEntry {
+    f_score: f32,
+    g_score: f32,
+    sequence: u64,
+    address: NodeAddress,
+}
+
+impl PartialEq for OpenSetEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenSetEntry {}
+
+impl PartialOrd for OpenSetEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenSetEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, since the standard binary heap is a max-heap.
+        other
+            .f_score
+            .total_cmp(&self.f_score)
+            .then_with(|| other.g_score.total_cmp(&self.g_score))
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
@@ -0,0 +1,170 @@
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::path_queries::reconstruct_path;
+use std::collections::HashMap;
+
+/// The shortest paths from a single source node to every node reachable from it.
+///
+/// For each reachable node, the tree holds the cost of the shortest path from the source
+/// and the link to the node's predecessor on that path. Paths to any reachable node
+/// can be reconstructed from it without searching the graph again.
+#[derive(Debug, Clone)]
+pub struct ShortestPathTree<R>
+where
+    R: Clone,
+{
+    source: NodeAddress,
+    /// Cost from the source along the shortest path.
+    distances: HashMap<NodeAddress, f32>,
+    /// For each node, which node it is reached from; `None` for the source.
+    came_from: HashMap<NodeAddress, Option<NodePathLink<R>>>,
+}
+
+impl<R> ShortestPathTree<R>
+where
+    R: Clone,
+{
+    /// Creates a tree that only contains the source node.
+    pub(crate) fn new(source: NodeAddress) -> Self {
+        let mut distances = HashMap::new();
+        let mut came_from = HashMap::new();
+        distances.insert(source.clone(), 0.0);
+        came_from.insert(source.clone(), None);
+        ShortestPathTree {
+            source,
+            distances,
+            came_from,
+        }
+    }
+
+    /// Records that `address` is reached at `distance` through the specified predecessor link.
+    pub(crate) fn update(&mut self, address: NodeAddress, distance: f32, link: NodePathLink<R>) {
+        self.distances.insert(address.clone(), distance);
+        self.came_from.insert(address, Some(link));
+    }
+
+    /// Gets the address of the source node.
+    pub fn source(&self) -> &NodeAddress {
+        &self.source
+    }
+
+    /// Determines whether the specified node is reachable from the source.
+    pub fn contains(&self, address: &NodeAddress) -> bool {
+        self.distances.contains_key(address)
+    }
+
+    /// Gets the cost of the shortest path from the source to the specified node,
+    /// or `None` if the node is not reachable.
+    pub fn distance(&self, address: &NodeAddress) -> Option<f32> {
+        self.distances.get(address).copied()
+    }
+
+    /// Gets the link to the predecessor of the specified node on its shortest path.
+    ///
+    /// The link holds the predecessor's address and the relation leading from it to the node.
+    /// Returns `None` for the source and for nodes that are not reachable.
+    pub fn predecessor(&self, address: &NodeAddress) -> Option<&NodePathLink<R>> {
+        self.came_from.get(address)?.as_ref()
+    }
+
+    /// Reconstructs the shortest path from the source to the specified node.
+    ///
+    /// ## Returns
+    ///
+    /// A path from the source to `target` or an empty vector if `target` is not reachable.
+    pub fn path_to(&self, target: &NodeAddress) -> Vec<NodePathLink<R>> {
+        if self.contains(target) {
+            reconstruct_path(&self.came_from, target.clone())
+        } else {
+            Vec::default()
+        }
+    }
+
+    /// Gets the costs of the shortest paths by node address.
+    pub fn distances(&self) -> &HashMap<NodeAddress, f32> {
+        &self.distances
+    }
+
+    /// Consumes the tree, returning the costs of the shortest paths by node address.
+    pub fn into_distances(self) -> HashMap<NodeAddress, f32> {
+        self.distances
+    }
+
+    /// Gets the number of reachable nodes, including the source.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    /// Determines whether the tree contains no nodes; never true, since the source is always reachable.
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, LondonGraphStationsCost};
+    #[cfg(feature = "examples-london")]
+    use crate::node_address::NodeAddress;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::bfs::BreadthFirstSearch;
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::dijkstra::DijkstraSearch;
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn paths_to_all_stations() {
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 0);
+        let tree = DijkstraSearch
+            .shortest_path_tree(&graph, start.clone(), &LondonGraphStationsCost)
+            .unwrap();
+        assert_eq!(tree.source(), &start);
+        assert_eq!(tree.len(), 198);
+
+        for (address, _) in graph.nodes() {
+            let expected = BreadthFirstSearch
+                .shortest_path(&graph, start.clone(), address.clone())
+                .unwrap();
+            let path = tree.path_to(&address);
+            assert_eq!(path.len(), expected.len());
+            assert_eq!(tree.contains(&address), !path.is_empty());
+            if let Some(distance) = tree.distance(&address) {
+                assert_eq!(distance, (path.len() - 1) as f32);
+                assert_eq!(path[0].address, start);
+                assert_eq!(path[path.len() - 1].address, address);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn predecessors() {
+        let graph = london_graph();
+        let tree = DijkstraSearch
+            .shortest_path_tree(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                &LondonGraphStationsCost,
+            )
+            .unwrap();
+
+        assert!(tree
+            .predecessor(&NodeAddress::from_local(graph.id(), 0))
+            .is_none());
+        assert!(tree
+            .predecessor(&NodeAddress::from_local(graph.id(), 199))
+            .is_none());
+        assert_eq!(
+            tree.distance(&NodeAddress::from_local(graph.id(), 199)),
+            None
+        );
+
+        let target = NodeAddress::from_local(graph.id(), 198);
+        let path = tree.path_to(&target);
+        let predecessor = tree.predecessor(&target).unwrap();
+        assert_eq!(predecessor.address, path[path.len() - 2].address);
+        assert_eq!(predecessor.relation, path[path.len() - 1].relation);
+    }
+}
@@ -0,0 +1,445 @@
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::path_queries::best_first::{best_first_search, SearchVisitor};
+use crate::path_queries::{AdmissibleHeuristic, PathCost};
+use crate::traversal::Control;
+use std::collections::{HashMap, HashSet};
+
+/// A solver for the `k` shortest loopless paths between two nodes, using Yen's algorithm.
+///
+/// Parallel relations between the same pair of nodes are distinct, so two paths may visit
+/// the same nodes in the same order and only differ in the relations they follow.
+#[derive(Debug, Default)]
+pub struct YenSearch;
+
+/// A path together with its total cost, as returned by the [`YenSearch`].
+#[derive(Debug, Clone)]
+pub struct RankedPath<R>
+where
+    R: Clone,
+{
+    /// The sum of the costs of all relations along the path.
+    pub cost: f32,
+    /// The path from the start to the target node.
+    pub path: Vec<NodePathLink<R>>,
+}
+
+impl YenSearch {
+    /// Determines up to `k` loopless paths from the `start` node to the `target` node,
+    /// in ascending order of their cost.
+    ///
+    /// Each path is found using an A* search, so the returned paths are only guaranteed to be
+    /// the cheapest ones if the heuristic is admissible.
+    ///
+    /// ## Arguments
+    ///
+    /// * `graph` - The graph to search on.
+    /// * `start` - The starting address in the graph.
+    /// * `target` - The target address in the graph.
+    /// * `k` - The maximum number of paths to return.
+    /// * `path_cost` - The cost of following a relation.
+    /// * `heuristic` - The estimated cost between two nodes.
+    ///
+    /// ## Returns
+    ///
+    /// The ranked paths; fewer than `k` if there are no more loopless paths,
+    /// or an empty vector if `target` is not reachable.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` or `target` does not address a node of the graph.
+    pub fn k_shortest_paths<G, P, H>(
+        &self,
+        graph: &G,
+        start: NodeAddress,
+        target: NodeAddress,
+        k: usize,
+        path_cost: &P,
+        heuristic: &H,
+    ) -> Result<Vec<RankedPath<G::Relation>>, NodeAddressError>
+    where
+        G: Graph,
+        G::Relation: Clone,
+        P: PathCost<G::Node, G::Relation>,
+        H: AdmissibleHeuristic<G::Node>,
+    {
+        graph.local_node_data_ref(&start)?;
+        let search = SpurSearch {
+            graph,
+            target: &target,
+            target_node: graph.local_node_data_ref(&target)?,
+            path_cost,
+            heuristic,
+        };
+
+        if k == 0 {
+            return Ok(Vec::default());
+        }
+
+        let mut accepted: Vec<Vec<Step<G::Relation>>> = Vec::new();
+        match search.run(&start, &HashSet::new(), &HashSet::new())? {
+            Some(path) => accepted.push(path),
+            None => return Ok(Vec::default()),
+        }
+
+        // The candidate paths, and the keys of all paths seen so far to avoid duplicates.
+        let mut candidates: Vec<Vec<Step<G::Relation>>> = Vec::new();
+        let mut seen: HashSet<Vec<(NodeAddress, Option<usize>)>> = HashSet::new();
+        seen.insert(path_key(&accepted[0]));
+
+        while accepted.len() < k {
+            let previous = accepted.last().expect("at least one path was accepted");
+
+            // Deviate from the previous path at each of its nodes, except for the target.
+            for spur_index in 0..previous.len() - 1 {
+                let root = &previous[..=spur_index];
+                let spur_addr = &root[spur_index].address;
+
+                // Block the relations that accepted paths with the same root continue along,
+                // and all nodes of the root to keep the path loopless.
+                let blocked_edges: HashSet<(NodeAddress, usize)> = accepted
+                    .iter()
+                    .filter(|path| {
+                        path.len() > spur_index + 1 && same_steps(&path[..=spur_index], root)
+                    })
+                    .filter_map(|path| path[spur_index + 1].edge)
+                    .map(|edge| (spur_addr.clone(), edge))
+                    .collect();
+                let blocked_nodes: HashSet<NodeAddress> = root[..spur_index]
+                    .iter()
+                    .map(|step| step.address.clone())
+                    .collect();
+
+                let Some(spur_path) = search.run(spur_addr, &blocked_nodes, &blocked_edges)? else {
+                    continue;
+                };
+
+                // The spur path starts at the spur node, which the root already contains.
+                let root_cost = root[spur_index].cost;
+                let mut path = root.to_vec();
+                path.extend(spur_path.into_iter().skip(1).map(|mut step| {
+                    step.cost += root_cost;
+                    step
+                }));
+
+                if seen.insert(path_key(&path)) {
+                    candidates.push(path);
+                }
+            }
+
+            // Accept the cheapest candidate; among equal costs, the one found first.
+            let Some(best) = candidates
+                .iter()
+                .enumerate()
+                .min_by(|(a_index, a), (b_index, b)| {
+                    total_cost(a)
+                        .total_cmp(&total_cost(b))
+                        .then(a_index.cmp(b_index))
+                })
+                .map(|(index, _)| index)
+            else {
+                break;
+            };
+            accepted.push(candidates.remove(best));
+        }
+
+        Ok(accepted
+            .into_iter()
+            .map(|steps| RankedPath {
+                cost: total_cost(&steps),
+                path: steps
+                    .into_iter()
+                    .map(|step| NodePathLink {
+                        relation: step.relation,
+                        address: step.address,
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+/// A step along a path, as tracked by the [`YenSearch`].
+#[derive(Debug, Clone)]
+struct Step<R> {
+    /// The address of the node reached in this step.
+    address: NodeAddress,
+    /// The relation followed to reach the node; `None` for the first step.
+    relation: Option<R>,
+    /// The index of the followed relation among the outgoing relations of the previous node.
+    edge: Option<usize>,
+    /// The cost of the path up to and including this step.
+    cost: f32,
+}
+
+/// Gets the total cost of a path.
+fn total_cost<R>(path: &[Step<R>]) -> f32 {
+    path.last().map_or(0.0, |step| step.cost)
+}
+
+/// Identifies a path by its nodes and the relations it follows.
+fn path_key<R>(path: &[Step<R>]) -> Vec<(NodeAddress, Option<usize>)> {
+    path.iter()
+        .map(|step| (step.address.clone(), step.edge))
+        .collect()
+}
+
+/// Determines whether two paths visit the same nodes along the same relations.
+fn same_steps<R>(a: &[Step<R>], b: &[Step<R>]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| a.address == b.address && a.edge == b.edge)
+}
+
+/// An A* search towards a fixed target that avoids blocked nodes and relations.
+struct SpurSearch<'a, G, P, H>
+where
+    G: Graph,
+{
+    graph: &'a G,
+    target: &'a NodeAddress,
+    target_node: &'a G::Node,
+    path_cost: &'a P,
+    heuristic: &'a H,
+}
+
+impl<'a, G, P, H> SpurSearch<'a, G, P, H>
+where
+    G: Graph,
+    G::Relation: Clone,
+    P: PathCost<G::Node, G::Relation>,
+    H: AdmissibleHeuristic<G::Node>,
+{
+    /// Searches a path from `start` to the target, never entering any of the `blocked_nodes`
+    /// and never following any of the `blocked_edges`, given as source address and relation index.
+    fn run(
+        &self,
+        start: &NodeAddress,
+        blocked_nodes: &HashSet<NodeAddress>,
+        blocked_edges: &HashSet<(NodeAddress, usize)>,
+    ) -> Result<Option<Vec<Step<G::Relation>>>, NodeAddressError> {
+        let mut visitor = SpurVisitor {
+            target: self.target,
+            blocked_nodes,
+            blocked_edges,
+            came_from: HashMap::new(),
+            reached: false,
+        };
+        best_first_search(
+            self.graph,
+            [start.clone()],
+            self.path_cost,
+            |_, node| self.heuristic.heuristic(node, self.target_node),
+            &mut visitor,
+        )?;
+
+        Ok(visitor
+            .reached
+            .then(|| self.reconstruct(start, &visitor.came_from, self.target.clone())))
+    }
+
+    /// Reconstructs the path to `current_addr`; each entry of `came_from` holds the
+    /// predecessor's address along with the relation, relation index and cost of the step.
+    fn reconstruct(
+        &self,
+        start: &NodeAddress,
+        came_from: &HashMap<NodeAddress, Step<G::Relation>>,
+        mut current_addr: NodeAddress,
+    ) -> Vec<Step<G::Relation>> {
+        let mut path = Vec::new();
+        while &current_addr != start {
+            let previous = &came_from[&current_addr];
+            path.push(Step {
+                address: current_addr,
+                ..previous.clone()
+            });
+            current_addr = previous.address.clone();
+        }
+        path.push(Step {
+            address: current_addr,
+            relation: None,
+            edge: None,
+            cost: 0.0,
+        });
+        path.reverse();
+        path
+    }
+}
+
+/// Tracks the [`SpurSearch`] towards its target, skipping blocked nodes and relations.
+struct SpurVisitor<'a, R> {
+    target: &'a NodeAddress,
+    blocked_nodes: &'a HashSet<NodeAddress>,
+    blocked_edges: &'a HashSet<(NodeAddress, usize)>,
+    /// For each reached node, the step that reaches it on the cheapest known path.
+    came_from: HashMap<NodeAddress, Step<R>>,
+    reached: bool,
+}
+
+impl<R> SearchVisitor<R> for SpurVisitor<'_, R>
+where
+    R: Clone,
+{
+    fn expand(&mut self, node: &NodeAddress, _g_score: f32) -> Control {
+        if node == self.target {
+            self.reached = true;
+            return Control::Break;
+        }
+        Control::Continue
+    }
+
+    fn follow(&mut self, from: &NodeAddress, edge: usize, to: &NodeAddress) -> bool {
+        !self.blocked_nodes.contains(to) && !self.blocked_edges.contains(&(from.clone(), edge))
+    }
+
+    fn improve(
+        &mut self,
+        from: &NodeAddress,
+        edge: usize,
+        relation: &R,
+        to: &NodeAddress,
+        g_score: f32,
+    ) {
+        self.came_from.insert(
+            to.clone(),
+            Step {
+                address: from.clone(),
+                relation: Some(relation.clone()),
+                edge: Some(edge),
+                cost: g_score,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, LondonGraphDistanceCost};
+    #[cfg(feature = "examples-london")]
+    use crate::path_queries::dijkstra::DijkstraSearch;
+    use crate::test_support::{RelationValue, ZeroHeuristic};
+
+    #[test]
+    fn it_works() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [c, d, e, f, g, h] = ["C", "D", "E", "F", "G", "H"].map(|name| graph.add(name));
+        for (from, to, cost) in [
+            (&c, &d, 3.0),
+            (&c, &e, 2.0),
+            (&d, &f, 4.0),
+            (&e, &d, 1.0),
+            (&e, &f, 2.0),
+            (&e, &g, 3.0),
+            (&f, &g, 2.0),
+            (&f, &h, 1.0),
+            (&g, &h, 2.0),
+        ] {
+            graph.link_to(from, to, cost).unwrap();
+        }
+
+        let paths = YenSearch
+            .k_shortest_paths(
+                &graph,
+                c.clone(),
+                h.clone(),
+                3,
+                &RelationValue,
+                &ZeroHeuristic,
+            )
+            .unwrap();
+
+        let ranked: Vec<_> = paths
+            .iter()
+            .map(|ranked| {
+                let nodes: Vec<_> = ranked
+                    .path
+                    .iter()
+                    .map(|link| *graph.node(&link.address).unwrap())
+                    .collect();
+                (ranked.cost, nodes)
+            })
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                (5.0, vec!["C", "E", "F", "H"]),
+                (7.0, vec!["C", "E", "G", "H"]),
+                (8.0, vec!["C", "D", "F", "H"]),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn top_london_routes() {
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 0);
+        let target = NodeAddress::from_local(graph.id(), 198);
+        let paths = YenSearch
+            .k_shortest_paths(
+                &graph,
+                start.clone(),
+                target.clone(),
+                5,
+                &LondonGraphDistanceCost,
+                &ZeroHeuristic,
+            )
+            .unwrap();
+        assert_eq!(paths.len(), 5);
+
+        let optimum = DijkstraSearch
+            .distances(&graph, start.clone(), &LondonGraphDistanceCost)
+            .unwrap()[&target];
+        assert_eq!(paths[0].cost, optimum);
+
+        for ranked in &paths {
+            assert_eq!(ranked.path[0].address, start);
+            assert_eq!(ranked.path[ranked.path.len() - 1].address, target);
+
+            let nodes: HashSet<_> = ranked.path.iter().map(|link| &link.address).collect();
+            assert_eq!(nodes.len(), ranked.path.len(), "path contains a loop");
+        }
+        for pair in paths.windows(2) {
+            assert!(pair[0].cost <= pair[1].cost);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unreachable() {
+        let graph = london_graph();
+        let paths = YenSearch
+            .k_shortest_paths(
+                &graph,
+                NodeAddress::from_local(graph.id(), 0),
+                NodeAddress::from_local(graph.id(), 199),
+                5,
+                &LondonGraphDistanceCost,
+                &ZeroHeuristic,
+            )
+            .unwrap();
+
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let result = YenSearch.k_shortest_paths(
+            &graph,
+            NodeAddress::from_local(graph.id(), 0),
+            NodeAddress::from_local(graph.id(), 1000),
+            5,
+            &LondonGraphDistanceCost,
+            &ZeroHeuristic,
+        );
+        assert!(matches!(result, Err(NodeAddressError::NodeNotFound(_))));
+    }
+}
@@ -1,25 +1,45 @@
 //! Provides a property graph type.
 
+use crate::csr_graph::CsrGraph;
 use crate::errors::NodeAddressError;
-use crate::node_address::NodeAddress;
-use crate::node_relation::NodeRelation;
+use crate::graph::{Direction, Graph};
+use crate::node_address::{GraphId, NodeAddress};
+use crate::node_relation::{
+    remove_relations, update_incoming, IncomingRelation, NodeRelation, NodeRelationRef,
+};
+use crate::node_slots::NodeSlots;
 use std::borrow::Borrow;
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::ops::{Deref, DerefMut};
 
 /// A property graph type.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PropertyGraph<T, R> {
-    nodes: Vec<Node<T>>,
-    // TODO: Use another vector here?
+    nodes: NodeSlots<Node<T>>,
+    /// The outgoing relations of each node, indexed by slot.
     // TODO: Use array-backed lists for this? Depends on the connectivity.
-    outgoing: HashMap<usize, Vec<NodeRelation<R>>>,
+    outgoing: Vec<Vec<NodeRelation<R>>>,
+    /// For each node, back-references to all relations leading to it, indexed by slot.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    incoming: Vec<Vec<IncomingRelation>>,
 }
 
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[cfg(feature = "boxed-nodes")]
 struct Node<T>(Box<NodeData<T>>);
 
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[cfg(not(feature = "boxed-nodes"))]
 struct Node<T>(NodeData<T>);
 
@@ -51,6 +71,7 @@ impl<T> DerefMut for Node<T> {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[allow(dead_code)]
 pub(crate) struct NodeData<T> {
     pub(crate) id: usize,
@@ -59,42 +80,170 @@ pub(crate) struct NodeData<T> {
 
 impl<T, R> PropertyGraph<T, R> {
     pub fn add(&mut self, data: T) -> NodeAddress {
-        let id = self.nodes.len();
-        let node = NodeData { id, data };
-        self.nodes.push(node.into());
-        self.outgoing.insert(id, Vec::new());
-        let address = NodeAddress::from_local(id);
+        let address = self.nodes.insert_with(|id| NodeData { id, data }.into());
+        match address {
+            NodeAddress::Local { index, .. } => {
+                // Recycled slots had their relations cleared on removal.
+                if index == self.outgoing.len() {
+                    self.outgoing.push(Vec::new());
+                    self.incoming.push(Vec::new());
+                }
+            }
+        }
         address
     }
 
+    /// Removes the node at the specified address, together with all relations from and to it.
+    ///
+    /// The slot of the removed node is recycled by later calls to [`add`](Self::add);
+    /// the removed address itself stays invalid and fails with [`NodeAddressError::NodeRemoved`].
+    ///
+    /// ## Returns
+    /// The data of the removed node.
+    pub fn remove(&mut self, address: &NodeAddress) -> Result<T, NodeAddressError> {
+        let node = self.nodes.remove(address)?;
+        let (outgoing, incoming) = match address {
+            NodeAddress::Local { index, .. } => (
+                std::mem::take(&mut self.outgoing[*index]),
+                std::mem::take(&mut self.incoming[*index]),
+            ),
+        };
+
+        let sources: HashSet<_> = incoming
+            .into_iter()
+            .map(|relation| relation.source)
+            .collect();
+        for source in &sources {
+            // Self-loops refer to the already removed node.
+            if source != address {
+                self.remove_outgoing(source, |relation| relation.address == *address)?;
+            }
+        }
+        for relation in &outgoing {
+            if let Ok(incoming) = self.incoming_mut(&relation.address) {
+                incoming.retain(|relation| relation.source != *address);
+            }
+        }
+        Ok(node.0.data)
+    }
+
+    /// Converts the graph into an immutable [`CsrGraph`] optimized for read access.
+    ///
+    /// All addresses of this graph remain valid in the frozen graph.
+    pub fn freeze(self) -> CsrGraph<T, R> {
+        let graph = self.nodes.graph();
+        let slots = self.nodes.into_slots().zip(self.outgoing);
+        CsrGraph::from_slots(
+            graph,
+            slots.map(|((generation, node), outgoing)| {
+                (generation, node.map(|node| (node.0.data, outgoing)))
+            }),
+        )
+    }
+
     /// Iterates the neighbors of a node given its [`NodeAddress`] under the condition that the node is locally available.
     pub fn iter_local_neighbors(
         &self,
         address: &NodeAddress,
     ) -> Result<impl Iterator<Item = &NodeRelation<R>>, NodeAddressError> {
+        self.nodes.get(address)?;
+
         #[allow(unreachable_patterns)]
         match address {
-            NodeAddress::Local(id) => {
-                if let Some(vec) = self.outgoing.get(id) {
-                    Ok(vec.iter())
-                } else {
-                    todo!()
-                }
-            }
+            NodeAddress::Local { index, .. } => match self.outgoing.get(*index) {
+                Some(vec) => Ok(vec.iter()),
+                None => Err(NodeAddressError::NodeNotFound(address.clone())),
+            },
             _ => Err(NodeAddressError::NodeNotLocal(address.clone())),
         }
     }
 
+    /// Iterates the neighbors of a node along relations of the specified [`Direction`].
+    ///
+    /// For incoming relations, the yielded address is the one of the node the relation originates from.
+    pub fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        let outgoing = self.iter_local_neighbors(address)?;
+        let sources = match address {
+            NodeAddress::Local { index, .. } => self.incoming.get(*index),
+        };
+
+        let outgoing = direction
+            .includes_outgoing()
+            .then(|| outgoing.map(NodeRelationRef::from));
+        let incoming = direction.includes_incoming().then(|| {
+            sources.into_iter().flatten().map(|incoming| {
+                let relations = match &incoming.source {
+                    NodeAddress::Local { index, .. } => &self.outgoing[*index],
+                };
+                NodeRelationRef {
+                    relation: &relations[incoming.position].relation,
+                    address: &incoming.source,
+                }
+            })
+        });
+
+        Ok(outgoing
+            .into_iter()
+            .flatten()
+            .chain(incoming.into_iter().flatten()))
+    }
+
     /// Gets a node's data given its [`NodeAddress`] under the condition that the node is locally available.
     pub(crate) fn local_node_data_ref(
         &self,
         address: &NodeAddress,
     ) -> Result<&T, NodeAddressError> {
-        #[allow(unreachable_patterns)]
-        match address {
-            NodeAddress::Local(id) => Ok(&self.nodes[*id].data),
-            _ => Err(NodeAddressError::NodeNotLocal(address.clone())),
-        }
+        Ok(&self.nodes.get(address)?.data)
+    }
+
+    /// Gets a node's data given its [`NodeAddress`].
+    pub fn node(&self, address: &NodeAddress) -> Result<&T, NodeAddressError> {
+        self.local_node_data_ref(address)
+    }
+
+    /// Gets a node's data given its [`NodeAddress`] for mutation.
+    pub fn node_mut(&mut self, address: &NodeAddress) -> Result<&mut T, NodeAddressError> {
+        Ok(&mut self.nodes.get_mut(address)?.data)
+    }
+
+    /// Iterates all nodes of the graph in slot order, together with their addresses.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &T)> {
+        self.nodes
+            .iter()
+            .map(|(address, node)| (address, &node.data))
+    }
+
+    /// Iterates all relations of the graph as `(from, relation, to)` triples,
+    /// ordered by the `from` node's slot and then by the order they were linked in.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeAddress, &R, NodeAddress)> {
+        self.nodes.iter().flat_map(move |(from, _)| {
+            let outgoing = match &from {
+                NodeAddress::Local { index, .. } => self.outgoing.get(*index),
+            };
+            outgoing
+                .into_iter()
+                .flatten()
+                .map(move |relation| (from.clone(), &relation.relation, relation.address.clone()))
+        })
+    }
+
+    /// Gets the id of the graph, which is part of every address the graph hands out.
+    pub fn id(&self) -> GraphId {
+        self.nodes.graph()
+    }
+
+    /// Gets the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Gets the number of relations in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.outgoing.iter().map(Vec::len).sum()
     }
 
     /// Creates a connection between the nodes at the `from` address and the `to` address.
@@ -106,18 +255,30 @@ impl<T, R> PropertyGraph<T, R> {
     /// * `from` - The node from which to link to another node.
     /// * `to` The node to link to.
     /// * `relation` - The type of relation.
-    pub fn link_to<A: Borrow<NodeAddress>>(&mut self, from: A, to: A, relation: R) {
+    ///
+    /// ## Errors
+    /// Fails without modifying the graph if either node does not exist.
+    pub fn link_to<A: Borrow<NodeAddress>>(
+        &mut self,
+        from: A,
+        to: A,
+        relation: R,
+    ) -> Result<(), NodeAddressError> {
         let from = from.borrow();
         let to = to.borrow();
-        match from {
-            NodeAddress::Local(from_idx) => {
-                let entry = self.outgoing.entry(*from_idx).or_insert(Vec::new());
-                entry.push(NodeRelation {
-                    relation,
-                    address: to.clone(),
-                });
-            }
-        }
+        self.local_node_data_ref(to)?;
+        let outgoing = self.outgoing_mut(from)?;
+        outgoing.push(NodeRelation {
+            relation,
+            address: to.clone(),
+        });
+
+        let position = outgoing.len() - 1;
+        self.incoming_mut(to)?.push(IncomingRelation {
+            source: from.clone(),
+            position,
+        });
+        Ok(())
     }
 
     /// Creates a bidirectional connection between the nodes at the `from` address and the `to` address.
@@ -129,27 +290,285 @@ impl<T, R> PropertyGraph<T, R> {
     /// * `from` - The node from which to link to another node.
     /// * `to` The node to link to.
     /// * `relation` - The type of relation.
-    pub fn link_bidir<A: Borrow<NodeAddress>>(&mut self, from: A, to: A, relation: R)
+    ///
+    /// ## Errors
+    /// Fails without modifying the graph if either node does not exist.
+    pub fn link_bidir<A: Borrow<NodeAddress>>(
+        &mut self,
+        from: A,
+        to: A,
+        relation: R,
+    ) -> Result<(), NodeAddressError>
     where
         R: Clone,
     {
-        self.link_to(from.borrow(), to.borrow(), relation.clone());
-        self.link_to(to.borrow(), from.borrow(), relation);
+        self.link_to(from.borrow(), to.borrow(), relation.clone())?;
+        self.link_to(to.borrow(), from.borrow(), relation)
+    }
+
+    /// Removes connections from the node at the `from` address to the node at the `to` address.
+    ///
+    /// Only relations for which the `predicate` returns `true` are removed;
+    /// pass `|_| true` to remove all connections between the two nodes.
+    ///
+    /// ## Arguments
+    /// * `from` - The node from which the connections originate.
+    /// * `to` The node the connections lead to.
+    /// * `predicate` - Selects the relations to remove.
+    ///
+    /// ## Returns
+    /// The removed relations, in the order they were linked.
+    pub fn unlink<A, F>(
+        &mut self,
+        from: A,
+        to: A,
+        mut predicate: F,
+    ) -> Result<Vec<R>, NodeAddressError>
+    where
+        A: Borrow<NodeAddress>,
+        F: FnMut(&R) -> bool,
+    {
+        let from = from.borrow();
+        let to = to.borrow();
+        self.local_node_data_ref(to)?;
+        self.remove_outgoing(from, |relation| {
+            relation.address == *to && predicate(&relation.relation)
+        })
+    }
+
+    /// Iterates the relations of all connections from the node at the `from` address to the node at the `to` address.
+    pub fn edges_between<'a>(
+        &'a self,
+        from: &NodeAddress,
+        to: &'a NodeAddress,
+    ) -> Result<impl Iterator<Item = &'a R>, NodeAddressError> {
+        self.local_node_data_ref(to)?;
+        Ok(self
+            .iter_local_neighbors(from)?
+            .filter(move |relation| relation.address == *to)
+            .map(|relation| &relation.relation))
+    }
+
+    /// Mutably iterates the relations of all connections from the node at the `from` address to the node at the `to` address.
+    pub fn edges_between_mut<'a>(
+        &'a mut self,
+        from: &NodeAddress,
+        to: &'a NodeAddress,
+    ) -> Result<impl Iterator<Item = &'a mut R>, NodeAddressError> {
+        self.local_node_data_ref(to)?;
+        let outgoing = self.outgoing_mut(from)?;
+        Ok(outgoing
+            .iter_mut()
+            .filter(move |relation| relation.address == *to)
+            .map(|relation| &mut relation.relation))
+    }
+
+    /// Determines whether the node at the `from` address has at least one connection to the node at the `to` address.
+    pub fn has_edge(&self, from: &NodeAddress, to: &NodeAddress) -> Result<bool, NodeAddressError> {
+        Ok(self.edges_between(from, to)?.next().is_some())
+    }
+
+    /// Removes the outgoing relations of the `from` node for which `remove` returns `true`,
+    /// keeping the back-references of the nodes they lead to in sync.
+    ///
+    /// ## Returns
+    /// The removed relations, in the order they were linked.
+    fn remove_outgoing<F>(
+        &mut self,
+        from: &NodeAddress,
+        remove: F,
+    ) -> Result<Vec<R>, NodeAddressError>
+    where
+        F: FnMut(&NodeRelation<R>) -> bool,
+    {
+        let outgoing = self.outgoing_mut(from)?;
+        let (removed, positions) = remove_relations(outgoing, remove);
+
+        // Relations behind the first removed one moved to a new position.
+        let first = positions
+            .iter()
+            .position(Option::is_none)
+            .unwrap_or(positions.len());
+        let targets: HashSet<_> = removed
+            .iter()
+            .chain(&outgoing[first..])
+            .map(|relation| relation.address.clone())
+            .collect();
+        for target in &targets {
+            // Self-loops of a node being removed refer to the already removed node.
+            if let Ok(incoming) = self.incoming_mut(target) {
+                update_incoming(incoming, from, &positions);
+            }
+        }
+        Ok(removed
+            .into_iter()
+            .map(|relation| relation.relation)
+            .collect())
+    }
+
+    /// Gets the outgoing relations of a node for mutation.
+    fn outgoing_mut(
+        &mut self,
+        address: &NodeAddress,
+    ) -> Result<&mut Vec<NodeRelation<R>>, NodeAddressError> {
+        self.nodes.get(address)?;
+        match address {
+            NodeAddress::Local { index, .. } => self
+                .outgoing
+                .get_mut(*index)
+                .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone())),
+        }
+    }
+
+    /// Gets the back-references to the relations leading to a node for mutation.
+    fn incoming_mut(
+        &mut self,
+        address: &NodeAddress,
+    ) -> Result<&mut Vec<IncomingRelation>, NodeAddressError> {
+        self.nodes.get(address)?;
+        match address {
+            NodeAddress::Local { index, .. } => self
+                .incoming
+                .get_mut(*index)
+                .ok_or_else(|| NodeAddressError::NodeNotFound(address.clone())),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, R> PropertyGraph<T, R> {
+    /// Recreates the incoming index of all nodes from their outgoing relations.
+    fn rebuild_incoming(&mut self) -> Result<(), NodeAddressError> {
+        let mut incoming = vec![Vec::new(); self.outgoing.len()];
+        for (index, relations) in self.outgoing.iter().enumerate() {
+            // Relations must neither originate from nor lead to missing nodes.
+            if relations.is_empty() {
+                continue;
+            }
+            let from = self.nodes.address_of(index).ok_or_else(|| {
+                NodeAddressError::NodeNotFound(NodeAddress::from_local(self.nodes.graph(), index))
+            })?;
+
+            for (position, relation) in relations.iter().enumerate() {
+                self.nodes.get(&relation.address)?;
+                let sources: &mut Vec<IncomingRelation> = match &relation.address {
+                    NodeAddress::Local { index, .. } => &mut incoming[*index],
+                };
+                sources.push(IncomingRelation {
+                    source: from.clone(),
+                    position,
+                });
+            }
+        }
+
+        self.incoming = incoming;
+        Ok(())
+    }
+}
+
+impl<T, R> Graph for PropertyGraph<T, R> {
+    type Node = T;
+    type Relation = R;
+
+    fn iter_local_neighbors(
+        &self,
+        address: &NodeAddress,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        Ok(PropertyGraph::iter_local_neighbors(self, address)?.map(NodeRelationRef::from))
+    }
+
+    fn iter_local_neighbors_directed(
+        &self,
+        address: &NodeAddress,
+        direction: Direction,
+    ) -> Result<impl Iterator<Item = NodeRelationRef<'_, R>>, NodeAddressError> {
+        PropertyGraph::iter_local_neighbors_directed(self, address, direction)
+    }
+
+    fn local_node_data_ref(&self, address: &NodeAddress) -> Result<&T, NodeAddressError> {
+        PropertyGraph::local_node_data_ref(self, address)
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = (NodeAddress, &T)> {
+        PropertyGraph::nodes(self)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (NodeAddress, &R, NodeAddress)> {
+        PropertyGraph::edges(self)
+    }
+
+    fn id(&self) -> GraphId {
+        PropertyGraph::id(self)
+    }
+
+    fn node_count(&self) -> usize {
+        PropertyGraph::node_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        PropertyGraph::edge_count(self)
     }
 }
 
 impl<T, R> Default for PropertyGraph<T, R> {
     fn default() -> Self {
         PropertyGraph {
-            nodes: Vec::new(),
-            outgoing: HashMap::default(),
+            nodes: NodeSlots::default(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, R> serde::Deserialize<'de> for PropertyGraph<T, R>
+where
+    T: serde::Deserialize<'de>,
+    R: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct Repr<T, R> {
+            nodes: NodeSlots<Node<T>>,
+            outgoing: Vec<Vec<NodeRelation<R>>>,
+        }
+
+        // The incoming index is not serialized; rebuilding it also validates all relation targets.
+        let Repr { nodes, outgoing } = Repr::deserialize(deserializer)?;
+        if outgoing.len() != nodes.slot_count() {
+            return Err(D::Error::custom("relations do not match the node slots"));
         }
+        let mut graph = PropertyGraph {
+            nodes,
+            outgoing,
+            incoming: Vec::new(),
+        };
+
+        // The restored graph receives a fresh id, which its relations are moved over to.
+        let serialized = graph.nodes.renew_graph();
+        let id = graph.id();
+        for relation in graph.outgoing.iter_mut().flatten() {
+            relation
+                .address
+                .rebind(serialized, id)
+                .map_err(D::Error::custom)?;
+        }
+        graph.rebuild_incoming().map_err(D::Error::custom)?;
+        Ok(graph)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    #[cfg(feature = "examples-london")]
     use crate::examples::london_graph::*;
+    #[cfg(feature = "examples-movies")]
     use crate::examples::movie_graph::*;
 
     #[test]
@@ -165,4 +584,249 @@ mod tests {
         let graph = london_graph();
         println!("{:?}", graph);
     }
+
+    #[test]
+    fn remove_node() {
+        let mut graph = PropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        let c = graph.add("c");
+        graph.link_bidir(&a, &b, ()).unwrap();
+        graph.link_bidir(&b, &c, ()).unwrap();
+
+        assert_eq!(graph.remove(&b).unwrap(), "b");
+        assert_eq!(graph.iter_local_neighbors(&a).unwrap().count(), 0);
+        assert_eq!(graph.iter_local_neighbors(&c).unwrap().count(), 0);
+        assert!(matches!(
+            graph.local_node_data_ref(&b),
+            Err(NodeAddressError::NodeRemoved(_))
+        ));
+
+        // The slot is recycled, but the stale address must not resolve to the new node.
+        let d = graph.add("d");
+        assert_ne!(b, d);
+        assert_eq!(*graph.local_node_data_ref(&d).unwrap(), "d");
+        assert!(graph.local_node_data_ref(&b).is_err());
+        assert!(graph.remove(&b).is_err());
+    }
+
+    #[test]
+    fn edges_between_nodes() {
+        let mut graph = PropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        graph.link_to(&a, &b, 10).unwrap();
+        graph.link_to(&a, &b, 20).unwrap();
+        graph.link_to(&b, &a, 30).unwrap();
+
+        assert!(graph.has_edge(&a, &b).unwrap());
+        assert_eq!(
+            graph.edges_between(&a, &b).unwrap().collect::<Vec<_>>(),
+            [&10, &20]
+        );
+
+        for relation in graph.edges_between_mut(&a, &b).unwrap() {
+            *relation += 1;
+        }
+        assert_eq!(
+            graph.edges_between(&a, &b).unwrap().collect::<Vec<_>>(),
+            [&11, &21]
+        );
+
+        assert_eq!(graph.unlink(&a, &b, |&r| r > 20).unwrap(), [21]);
+        assert_eq!(graph.unlink(&a, &b, |_| true).unwrap(), [11]);
+        assert!(!graph.has_edge(&a, &b).unwrap());
+        assert!(graph.has_edge(&b, &a).unwrap());
+    }
+
+    #[test]
+    fn directed_neighbors() {
+        let mut graph = PropertyGraph::default();
+        let a = graph.add("a");
+        let b = graph.add("b");
+        let c = graph.add("c");
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&c, &b, "cb").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+
+        let incoming: Vec<_> = graph
+            .iter_local_neighbors_directed(&b, Direction::Incoming)
+            .unwrap()
+            .map(|relation| (*relation.relation, relation.address.clone()))
+            .collect();
+        assert_eq!(incoming, [("ab", a.clone()), ("cb", c.clone())]);
+
+        let both: Vec<_> = graph
+            .iter_local_neighbors_directed(&b, Direction::Both)
+            .unwrap()
+            .map(|relation| *relation.relation)
+            .collect();
+        assert_eq!(both, ["bc", "ab", "cb"]);
+
+        graph.unlink(&a, &b, |_| true).unwrap();
+        graph.remove(&c).unwrap();
+        assert_eq!(
+            graph
+                .iter_local_neighbors_directed(&b, Direction::Both)
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn incoming_after_unlink() {
+        let mut graph = PropertyGraph::default();
+        let [a, b, c] = ["a", "b", "c"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, "ab1").unwrap();
+        graph.link_to(&a, &c, "ac1").unwrap();
+        graph.link_to(&a, &b, "ab2").unwrap();
+        graph.link_to(&a, &c, "ac2").unwrap();
+
+        let incoming =
+            |graph: &PropertyGraph<&str, &'static str>, address| -> Vec<(&str, NodeAddress)> {
+                graph
+                    .iter_local_neighbors_directed(address, Direction::Incoming)
+                    .unwrap()
+                    .map(|relation| (*relation.relation, relation.address.clone()))
+                    .collect()
+            };
+
+        // Removing a relation moves the relations behind it.
+        assert_eq!(
+            graph.unlink(&a, &b, |relation| *relation == "ab1").unwrap(),
+            ["ab1"]
+        );
+        assert_eq!(incoming(&graph, &b), [("ab2", a.clone())]);
+        assert_eq!(
+            incoming(&graph, &c),
+            [("ac1", a.clone()), ("ac2", a.clone())]
+        );
+
+        graph.remove(&b).unwrap();
+        assert_eq!(
+            incoming(&graph, &c),
+            [("ac1", a.clone()), ("ac2", a.clone())]
+        );
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn unknown_addresses() {
+        let mut graph = PropertyGraph::default();
+        let a = graph.add("a");
+        let unknown = NodeAddress::from_local(graph.id(), 42);
+
+        assert!(matches!(
+            graph.local_node_data_ref(&unknown),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+        assert!(matches!(
+            graph.iter_local_neighbors(&unknown),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+        assert!(graph.link_to(&a, &unknown, ()).is_err());
+        assert!(graph.link_to(&unknown, &a, ()).is_err());
+        assert!(graph.has_edge(&a, &unknown).is_err());
+        assert_eq!(graph.iter_local_neighbors(&a).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn node_and_edge_access() {
+        let mut graph = PropertyGraph::default();
+        let a = graph.add(1);
+        let b = graph.add(2);
+        let c = graph.add(3);
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+        graph.link_to(&a, &c, "ac").unwrap();
+
+        *graph.node_mut(&b).unwrap() += 10;
+        assert_eq!(*graph.node(&b).unwrap(), 12);
+
+        graph.remove(&c).unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(
+            graph.nodes().collect::<Vec<_>>(),
+            [(a.clone(), &1), (b.clone(), &12)]
+        );
+        assert_eq!(graph.edges().collect::<Vec<_>>(), [(a, &"ab", b)]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde", feature = "examples-london"))]
+    fn serde_round_trip() {
+        let mut graph = london_property_graph();
+        graph
+            .remove(&NodeAddress::from_local(graph.id(), 3))
+            .unwrap();
+
+        let json = serde_json::to_string(&graph).unwrap();
+        let mut restored: PropertyGraph<Station, ConnectionType> =
+            serde_json::from_str(&json).unwrap();
+
+        // The restored graph has an id of its own, but the same nodes in the same slots.
+        assert_ne!(restored.id(), graph.id());
+        let rebind = |address: NodeAddress| match address {
+            NodeAddress::Local {
+                index, generation, ..
+            } => NodeAddress::from_local_generation(restored.id(), index, generation),
+        };
+        assert_eq!(restored.node_count(), graph.node_count());
+        assert!(restored
+            .edges()
+            .eq(graph
+                .edges()
+                .map(|(from, relation, to)| (rebind(from), relation, rebind(to)))));
+        assert!(matches!(
+            restored.node(&NodeAddress::from_local(restored.id(), 3)),
+            Err(NodeAddressError::NodeRemoved(_))
+        ));
+        assert!(matches!(
+            restored.node(&NodeAddress::from_local(graph.id(), 0)),
+            Err(NodeAddressError::ForeignGraph(_))
+        ));
+        assert_eq!(
+            restored
+                .iter_local_neighbors_directed(
+                    &NodeAddress::from_local(restored.id(), 10),
+                    Direction::Incoming
+                )
+                .unwrap()
+                .count(),
+            graph
+                .iter_local_neighbors_directed(
+                    &NodeAddress::from_local(graph.id(), 10),
+                    Direction::Incoming
+                )
+                .unwrap()
+                .count()
+        );
+
+        // Restoring the same data twice yields two distinct graphs.
+        let again: PropertyGraph<Station, ConnectionType> = serde_json::from_str(&json).unwrap();
+        assert_ne!(again.id(), restored.id());
+
+        // The vacated slot is reused with the next generation.
+        let station = serde_json::from_str(r#"{"id": 201, "x": 0, "y": 0}"#).unwrap();
+        assert_eq!(
+            restored.add(station),
+            NodeAddress::from_local_generation(restored.id(), 3, 1)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_rejects_dangling_relations() {
+        let mut graph = PropertyGraph::default();
+        let a = graph.add(1);
+        let b = graph.add(2);
+        graph.link_to(&a, &b, ()).unwrap();
+
+        let json = serde_json::to_string(&graph)
+            .unwrap()
+            .replace(r#""index":1"#, r#""index":7"#);
+        assert!(serde_json::from_str::<PropertyGraph<i32, ()>>(&json).is_err());
+    }
 }
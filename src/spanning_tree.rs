@@ -0,0 +1,326 @@
+//! Provides minimum spanning trees and forests.
+//!
+//! Relations are treated as undirected edges weighted by a [`PathCost`]; relations with
+//! an infinite cost are never part of a spanning forest. For graphs that are not connected,
+//! the result is a minimum spanning tree of every weakly connected component.
+
+use crate::errors::NodeAddressError;
+use crate::graph::{Direction, Graph};
+use crate::node_address::NodeAddress;
+use crate::path_queries::PathCost;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// A relation that is part of a spanning forest.
+#[derive(Debug, Clone)]
+pub struct SpanningEdge<R> {
+    /// The node the relation originates from.
+    pub from: NodeAddress,
+    /// The relation.
+    pub relation: R,
+    /// The node the relation leads to.
+    pub to: NodeAddress,
+    /// The cost of the relation.
+    pub cost: f32,
+}
+
+/// A minimum spanning forest, as an edge list.
+#[derive(Debug, Clone)]
+pub struct SpanningForest<R> {
+    /// The relations of the forest, in the order they were selected.
+    pub edges: Vec<SpanningEdge<R>>,
+    /// The sum of the costs of all relations of the forest.
+    pub cost: f32,
+}
+
+impl<R> SpanningForest<R> {
+    fn new() -> Self {
+        SpanningForest {
+            edges: Vec::new(),
+            cost: 0.0,
+        }
+    }
+
+    fn push(&mut self, edge: SpanningEdge<R>) {
+        self.cost += edge.cost;
+        self.edges.push(edge);
+    }
+}
+
+/// Determines a minimum spanning forest using Kruskal's algorithm.
+///
+/// Relations of equal cost are considered in the order the graph yields them.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn kruskal<G, P>(
+    graph: &G,
+    path_cost: &P,
+) -> Result<SpanningForest<G::Relation>, NodeAddressError>
+where
+    G: Graph,
+    G::Relation: Clone,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let mut sets = DisjointSets::default();
+    let mut edges = Vec::with_capacity(graph.edge_count());
+    for (from, relation, to) in graph.edges() {
+        let cost = path_cost.path_cost(
+            graph.local_node_data_ref(&from)?,
+            graph.local_node_data_ref(&to)?,
+            relation,
+        );
+        if cost < f32::INFINITY {
+            edges.push((cost, from, relation, to));
+        }
+    }
+    edges.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut forest = SpanningForest::new();
+    for (cost, from, relation, to) in edges {
+        if sets.union(&from, &to) {
+            forest.push(SpanningEdge {
+                from,
+                relation: relation.clone(),
+                to,
+                cost,
+            });
+        }
+    }
+    Ok(forest)
+}
+
+/// Determines a minimum spanning forest using Prim's algorithm.
+///
+/// A tree is grown from each node that is not yet part of the forest, in the order the graph
+/// yields its nodes. Relations of equal cost are selected in the order they were discovered.
+///
+/// ## Errors
+///
+/// Fails if the graph contains relations to nodes that do not exist.
+pub fn prim<G, P>(graph: &G, path_cost: &P) -> Result<SpanningForest<G::Relation>, NodeAddressError>
+where
+    G: Graph,
+    G::Relation: Clone,
+    P: PathCost<G::Node, G::Relation>,
+{
+    let mut forest = SpanningForest::new();
+    let mut in_forest = HashSet::new();
+    let mut candidates = BinaryHeap::new();
+    let mut sequence = 0;
+
+    for (root, _) in graph.nodes() {
+        if !in_forest.insert(root.clone()) {
+            continue;
+        }
+
+        let mut current_addr = root;
+        loop {
+            // Offer all relations of the newly added node that lead out of the tree.
+            let current_node = graph.local_node_data_ref(&current_addr)?;
+            for direction in [Direction::Outgoing, Direction::Incoming] {
+                for neighbor in graph.iter_local_neighbors_directed(&current_addr, direction)? {
+                    if in_forest.contains(neighbor.address) {
+                        continue;
+                    }
+
+                    let neighbor_node = graph.local_node_data_ref(neighbor.address)?;
+                    let (from, to, cost) = match direction {
+                        Direction::Incoming => (
+                            neighbor.address.clone(),
+                            current_addr.clone(),
+                            path_cost.path_cost(neighbor_node, current_node, neighbor.relation),
+                        ),
+                        _ => (
+                            current_addr.clone(),
+                            neighbor.address.clone(),
+                            path_cost.path_cost(current_node, neighbor_node, neighbor.relation),
+                        ),
+                    };
+                    if cost < f32::INFINITY {
+                        candidates.push(Candidate {
+                            sequence,
+                            edge: SpanningEdge {
+                                from,
+                                relation: neighbor.relation,
+                                to,
+                                cost,
+                            },
+                            outside: neighbor.address.clone(),
+                        });
+                        sequence += 1;
+                    }
+                }
+            }
+
+            // Select the cheapest relation that still leads out of the tree.
+            let Some(candidate) = std::iter::from_fn(|| candidates.pop())
+                .find(|candidate| !in_forest.contains(&candidate.outside))
+            else {
+                break;
+            };
+
+            in_forest.insert(candidate.outside.clone());
+            current_addr = candidate.outside;
+            forest.push(SpanningEdge {
+                from: candidate.edge.from,
+                relation: candidate.edge.relation.clone(),
+                to: candidate.edge.to,
+                cost: candidate.edge.cost,
+            });
+        }
+    }
+
+    Ok(forest)
+}
+
+/// A relation offered to Prim's algorithm, borrowing the relation from the graph.
+struct Candidate<'a, R> {
+    /// The order in which candidates were offered, used as a tie breaker.
+    sequence: u64,
+    edge: SpanningEdge<&'a R>,
+    /// The end of the relation that is not yet part of the tree.
+    outside: NodeAddress,
+}
+
+impl<'a, R> PartialEq for Candidate<'a, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, R> Eq for Candidate<'a, R> {}
+
+impl<'a, R> PartialOrd for Candidate<'a, R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, R> Ord for Candidate<'a, R> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, since the standard binary heap is a max-heap.
+        other
+            .edge
+            .cost
+            .total_cmp(&self.edge.cost)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// A union-find structure over node addresses.
+#[derive(Default)]
+struct DisjointSets {
+    parent: HashMap<NodeAddress, NodeAddress>,
+    rank: HashMap<NodeAddress, usize>,
+}
+
+impl DisjointSets {
+    /// Finds the representative of the set containing `address`.
+    fn find(&mut self, address: &NodeAddress) -> NodeAddress {
+        let mut root = address.clone();
+        while let Some(parent) = self.parent.get(&root) {
+            root = parent.clone();
+        }
+
+        // Compress the path, so that later lookups are fast.
+        let mut current = address.clone();
+        while current != root {
+            current = self
+                .parent
+                .insert(current, root.clone())
+                .expect("nodes other than the root have a parent");
+        }
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returning whether they were distinct.
+    fn union(&mut self, a: &NodeAddress, b: &NodeAddress) -> bool {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a == b {
+            return false;
+        }
+
+        let rank_a = self.rank.get(&a).copied().unwrap_or_default();
+        let rank_b = self.rank.get(&b).copied().unwrap_or_default();
+        match rank_a.cmp(&rank_b) {
+            Ordering::Less => {
+                self.parent.insert(a, b);
+            }
+            Ordering::Greater => {
+                self.parent.insert(b, a);
+            }
+            Ordering::Equal => {
+                self.rank.insert(a.clone(), rank_a + 1);
+                self.parent.insert(b, a);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "examples-london")]
+    use crate::components::weakly_connected_components;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::{london_graph, LondonGraphDistanceCost};
+    use crate::test_support::RelationValue;
+
+    #[test]
+    fn it_works() {
+        // A square a-b-c-d with a diagonal a-c, and a separate pair e-f.
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d, e, f] = ["a", "b", "c", "d", "e", "f"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, 1.0).unwrap();
+        graph.link_to(&b, &c, 4.0).unwrap();
+        graph.link_to(&c, &d, 1.0).unwrap();
+        graph.link_to(&d, &a, 3.0).unwrap();
+        graph.link_to(&c, &a, 2.0).unwrap();
+        graph.link_to(&f, &e, 5.0).unwrap();
+        graph.link_to(&e, &e, 0.0).unwrap();
+        graph.link_to(&e, &f, f32::INFINITY).unwrap();
+
+        for forest in [
+            kruskal(&graph, &RelationValue).unwrap(),
+            prim(&graph, &RelationValue).unwrap(),
+        ] {
+            assert_eq!(forest.cost, 9.0);
+            let mut relations: Vec<_> = forest.edges.iter().map(|edge| edge.relation).collect();
+            relations.sort_by(f32::total_cmp);
+            assert_eq!(relations, [1.0, 1.0, 2.0, 5.0]);
+
+            let diagonal = forest
+                .edges
+                .iter()
+                .find(|edge| edge.relation == 2.0)
+                .unwrap();
+            assert_eq!((&diagonal.from, &diagonal.to), (&c, &a));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn london_backbone() {
+        let graph = london_graph();
+        let components = weakly_connected_components(&graph).unwrap();
+
+        let kruskal = kruskal(&graph, &LondonGraphDistanceCost).unwrap();
+        let prim = prim(&graph, &LondonGraphDistanceCost).unwrap();
+        for forest in [&kruskal, &prim] {
+            assert_eq!(forest.edges.len(), graph.node_count() - components.count());
+
+            let connected: HashSet<_> = forest
+                .edges
+                .iter()
+                .flat_map(|edge| [&edge.from, &edge.to])
+                .collect();
+            assert_eq!(connected.len(), forest.edges.len() + 1);
+        }
+        assert!((kruskal.cost - prim.cost).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,28 @@
+//! Provides the cost functions and heuristics shared by the unit tests.
+
+use crate::flow::Capacity;
+use crate::path_queries::{AdmissibleHeuristic, PathCost};
+
+/// Uses the relation's value as its cost, weight or capacity.
+pub(crate) struct RelationValue;
+
+impl<N> PathCost<N, f32> for RelationValue {
+    fn path_cost(&self, _from: &N, _to: &N, relation: &f32) -> f32 {
+        *relation
+    }
+}
+
+impl<N> Capacity<N, f32> for RelationValue {
+    fn capacity(&self, _from: &N, _to: &N, relation: &f32) -> f32 {
+        *relation
+    }
+}
+
+/// A trivially admissible heuristic.
+pub(crate) struct ZeroHeuristic;
+
+impl<N> AdmissibleHeuristic<N> for ZeroHeuristic {
+    fn heuristic(&self, _from: &N, _to: &N) -> f32 {
+        0.0
+    }
+}
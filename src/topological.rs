@@ -0,0 +1,193 @@
+//! Provides topological ordering and cycle detection.
+
+use crate::errors::{NodeAddressError, TopologicalOrderError};
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use crate::node_relation::NodePathLink;
+use crate::traversal::{depth_first_visit, Control, Visitor};
+use std::collections::HashMap;
+
+/// Orders the nodes of a graph such that every relation leads from an earlier to a later node.
+///
+/// For a dependency graph whose relations point from each task to the tasks depending on it,
+/// this is an order in which the tasks can be scheduled.
+///
+/// ## Returns
+///
+/// All nodes of the graph in topological order.
+///
+/// ## Errors
+///
+/// Fails with [`TopologicalOrderError::Cycle`] if the graph contains a cycle, in which case no
+/// such order exists. The error holds one of the cycles as a path that starts and ends at the
+/// same node; for a node related to itself, the path consists of that node twice.
+pub fn topological_order<G>(
+    graph: &G,
+) -> Result<Vec<NodeAddress>, TopologicalOrderError<G::Relation>>
+where
+    G: Graph,
+    G::Relation: Clone,
+{
+    let mut visitor = CycleDetector::default();
+    let control = depth_first_visit(
+        graph,
+        graph.nodes().map(|(address, _)| address),
+        &mut visitor,
+    )?;
+
+    if control == Control::Break {
+        let (from, to) = visitor
+            .back_edge
+            .expect("the traversal stopped at a back edge");
+        return Err(TopologicalOrderError::Cycle(reconstruct_cycle(
+            graph,
+            &visitor.parent,
+            from,
+            to,
+        )?));
+    }
+
+    // A node finishes only after all nodes reachable from it.
+    visitor.finished.reverse();
+    Ok(visitor.finished)
+}
+
+/// Records the depth-first search tree and stops at the first back edge.
+#[derive(Default)]
+struct CycleDetector {
+    /// The parent of each node in the depth-first search tree.
+    parent: HashMap<NodeAddress, NodeAddress>,
+    /// The nodes in the order they were finished.
+    finished: Vec<NodeAddress>,
+    /// The back edge that closes a cycle, if one was found.
+    back_edge: Option<(NodeAddress, NodeAddress)>,
+}
+
+impl<R> Visitor<R> for CycleDetector {
+    fn tree_edge(&mut self, from: &NodeAddress, _relation: &R, to: &NodeAddress) -> Control {
+        self.parent.insert(to.clone(), from.clone());
+        Control::Continue
+    }
+
+    fn back_edge(&mut self, from: &NodeAddress, _relation: &R, to: &NodeAddress) -> Control {
+        self.back_edge = Some((from.clone(), to.clone()));
+        Control::Break
+    }
+
+    fn finish(&mut self, node: &NodeAddress) -> Control {
+        self.finished.push(node.clone());
+        Control::Continue
+    }
+}
+
+/// Reconstructs the cycle closed by the back edge `from -> to`, where `to` is an ancestor
+/// of `from` in the depth-first search tree.
+///
+/// Where several relations connect two consecutive nodes of the cycle, the first one is used.
+fn reconstruct_cycle<G>(
+    graph: &G,
+    parent: &HashMap<NodeAddress, NodeAddress>,
+    from: NodeAddress,
+    to: NodeAddress,
+) -> Result<Vec<NodePathLink<G::Relation>>, NodeAddressError>
+where
+    G: Graph,
+    G::Relation: Clone,
+{
+    // Walk up the tree from the end of the back edge to its target.
+    let mut nodes = vec![to.clone(), from.clone()];
+    let mut current_addr = from;
+    while current_addr != to {
+        current_addr = parent[&current_addr].clone();
+        nodes.push(current_addr.clone());
+    }
+    nodes.reverse();
+
+    let mut cycle = vec![NodePathLink {
+        relation: None,
+        address: nodes[0].clone(),
+    }];
+    for step in nodes.windows(2) {
+        let relation = graph
+            .iter_local_neighbors(&step[0])?
+            .find(|neighbor| neighbor.address == &step[1])
+            .expect("consecutive nodes of the cycle are related")
+            .relation;
+        cycle.push(NodePathLink {
+            relation: Some(relation.clone()),
+            address: step[1].clone(),
+        });
+    }
+    Ok(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::london_graph;
+
+    #[test]
+    fn dependency_order() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [fetch, configure, build, test, package] =
+            ["fetch", "configure", "build", "test", "package"].map(|task| graph.add(task));
+        graph.link_to(&build, &package, ()).unwrap();
+        graph.link_to(&fetch, &configure, ()).unwrap();
+        graph.link_to(&configure, &build, ()).unwrap();
+        graph.link_to(&build, &test, ()).unwrap();
+        graph.link_to(&test, &package, ()).unwrap();
+
+        let order = topological_order(&graph).unwrap();
+        assert_eq!(order, [fetch, configure, build, test, package]);
+    }
+
+    #[test]
+    fn reports_cycle() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+        graph.link_to(&c, &d, "cd").unwrap();
+        graph.link_to(&d, &b, "db").unwrap();
+
+        let Err(TopologicalOrderError::Cycle(cycle)) = topological_order(&graph) else {
+            panic!("expected a cycle");
+        };
+        let links: Vec<_> = cycle
+            .iter()
+            .map(|link| (link.address.clone(), link.relation))
+            .collect();
+        assert_eq!(
+            links,
+            [
+                (b.clone(), None),
+                (c, Some("bc")),
+                (d, Some("cd")),
+                (b, Some("db"))
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_self_loop() {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let a = graph.add("a");
+        graph.link_to(&a, &a, "aa").unwrap();
+
+        let Err(TopologicalOrderError::Cycle(cycle)) = topological_order(&graph) else {
+            panic!("expected a cycle");
+        };
+        assert_eq!(cycle.len(), 2);
+        assert_eq!(cycle[1].address, a);
+        assert_eq!(cycle[1].relation, Some("aa"));
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn bidirectional_relations_are_cycles() {
+        let result = topological_order(&london_graph());
+        assert!(matches!(result, Err(TopologicalOrderError::Cycle(cycle)) if cycle.len() == 3));
+    }
+}
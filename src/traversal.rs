@@ -0,0 +1,558 @@
+//! Provides lazy graph traversals and visitor-driven searches.
+//!
+//! The [`Bfs`] and [`Dfs`] iterators yield node addresses in traversal order, so that a walk
+//! can be stopped at any time. For algorithms that need to know how each relation was
+//! classified during the traversal, [`depth_first_visit`] and [`breadth_first_visit`] report
+//! events to a [`Visitor`].
+
+use crate::errors::NodeAddressError;
+use crate::graph::Graph;
+use crate::node_address::NodeAddress;
+use std::collections::{HashSet, VecDeque};
+
+/// A lazy breadth-first traversal, yielding each node reachable from the start exactly once.
+///
+/// Nodes are yielded in the order they are discovered; the start node comes first.
+#[derive(Debug)]
+pub struct Bfs<'a, G> {
+    graph: &'a G,
+    queue: VecDeque<NodeAddress>,
+    discovered: HashSet<NodeAddress>,
+}
+
+impl<'a, G> Bfs<'a, G>
+where
+    G: Graph,
+{
+    /// Creates a breadth-first traversal of the specified graph, starting at the `start` node.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` does not address a node of the graph.
+    pub fn new(graph: &'a G, start: NodeAddress) -> Result<Self, NodeAddressError> {
+        graph.local_node_data_ref(&start)?;
+        let mut discovered = HashSet::new();
+        discovered.insert(start.clone());
+        Ok(Bfs {
+            graph,
+            queue: VecDeque::from([start]),
+            discovered,
+        })
+    }
+}
+
+impl<'a, G> Iterator for Bfs<'a, G>
+where
+    G: Graph,
+{
+    type Item = Result<NodeAddress, NodeAddressError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current_addr = self.queue.pop_front()?;
+        let neighbors = match self.graph.iter_local_neighbors(&current_addr) {
+            Ok(neighbors) => neighbors,
+            Err(error) => return Some(Err(error)),
+        };
+
+        for neighbor in neighbors {
+            if self.discovered.insert(neighbor.address.clone()) {
+                self.queue.push_back(neighbor.address.clone());
+            }
+        }
+
+        Some(Ok(current_addr))
+    }
+}
+
+/// A lazy depth-first traversal, yielding each node reachable from the start exactly once.
+///
+/// Nodes are yielded in the order a recursive depth-first search would discover them,
+/// following the relations of each node in the order the graph yields them.
+#[derive(Debug)]
+pub struct Dfs<'a, G> {
+    graph: &'a G,
+    stack: Vec<NodeAddress>,
+    discovered: HashSet<NodeAddress>,
+}
+
+impl<'a, G> Dfs<'a, G>
+where
+    G: Graph,
+{
+    /// Creates a depth-first traversal of the specified graph, starting at the `start` node.
+    ///
+    /// ## Errors
+    ///
+    /// Fails if `start` does not address a node of the graph.
+    pub fn new(graph: &'a G, start: NodeAddress) -> Result<Self, NodeAddressError> {
+        graph.local_node_data_ref(&start)?;
+        Ok(Dfs {
+            graph,
+            stack: vec![start],
+            discovered: HashSet::new(),
+        })
+    }
+}
+
+impl<'a, G> Iterator for Dfs<'a, G>
+where
+    G: Graph,
+{
+    type Item = Result<NodeAddress, NodeAddressError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Nodes may be pushed several times; only their first visit counts.
+        let current_addr = loop {
+            let address = self.stack.pop()?;
+            if self.discovered.insert(address.clone()) {
+                break address;
+            }
+        };
+
+        let neighbors = match self.graph.iter_local_neighbors(&current_addr) {
+            Ok(neighbors) => neighbors,
+            Err(error) => return Some(Err(error)),
+        };
+
+        // Push in reverse, so that the first neighbor is visited first.
+        let start = self.stack.len();
+        self.stack.extend(
+            neighbors
+                .filter(|neighbor| !self.discovered.contains(neighbor.address))
+                .map(|neighbor| neighbor.address.clone()),
+        );
+        self.stack[start..].reverse();
+
+        Some(Ok(current_addr))
+    }
+}
+
+/// Controls how a traversal continues after a [`Visitor`] event.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Control {
+    /// Continues the traversal.
+    Continue,
+    /// Skips the node or relation the event was reported for.
+    ///
+    /// Returned from [`Visitor::discover`], the relations of the node are not followed.
+    /// Returned from [`Visitor::tree_edge`], the relation is not followed and its target
+    /// is not discovered through it. Has the same effect as [`Control::Continue`] for other events.
+    Prune,
+    /// Stops the traversal.
+    Break,
+}
+
+/// Receives the events of a [`depth_first_visit`] or [`breadth_first_visit`].
+///
+/// All methods default to continuing the traversal, so that implementations
+/// only need to provide the events they are interested in.
+pub trait Visitor<R> {
+    /// A node is reached for the first time.
+    fn discover(&mut self, _node: &NodeAddress) -> Control {
+        Control::Continue
+    }
+
+    /// A relation leads to a node that was not discovered yet, making it part of the search tree.
+    fn tree_edge(&mut self, _from: &NodeAddress, _relation: &R, _to: &NodeAddress) -> Control {
+        Control::Continue
+    }
+
+    /// A relation leads to a node whose relations are still being followed, i.e. an ancestor
+    /// in the depth-first search tree. Back edges indicate a cycle.
+    ///
+    /// Only reported by the [`depth_first_visit`].
+    fn back_edge(&mut self, _from: &NodeAddress, _relation: &R, _to: &NodeAddress) -> Control {
+        Control::Continue
+    }
+
+    /// A relation leads to a node that was discovered before and is not part of the search tree.
+    ///
+    /// In a [`depth_first_visit`], these are relations to nodes that were already finished,
+    /// i.e. forward and cross edges. In a [`breadth_first_visit`], these are all relations
+    /// to previously discovered nodes.
+    fn cross_edge(&mut self, _from: &NodeAddress, _relation: &R, _to: &NodeAddress) -> Control {
+        Control::Continue
+    }
+
+    /// All relations of a node were followed.
+    fn finish(&mut self, _node: &NodeAddress) -> Control {
+        Control::Continue
+    }
+}
+
+/// Performs a depth-first search from each of the `starts` in turn, reporting events to the `visitor`.
+///
+/// Start nodes that were already discovered from an earlier start are skipped,
+/// so each node is discovered and finished at most once.
+///
+/// ## Returns
+///
+/// [`Control::Break`] if the visitor stopped the traversal, [`Control::Continue`] otherwise.
+///
+/// ## Errors
+///
+/// Fails if any of the `starts` does not address a node of the graph.
+pub fn depth_first_visit<G, I, V>(
+    graph: &G,
+    starts: I,
+    visitor: &mut V,
+) -> Result<Control, NodeAddressError>
+where
+    G: Graph,
+    I: IntoIterator<Item = NodeAddress>,
+    V: Visitor<G::Relation>,
+{
+    let mut discovered = HashSet::new();
+    let mut finished = HashSet::new();
+
+    for start in starts {
+        graph.local_node_data_ref(&start)?;
+        if !discovered.insert(start.clone()) {
+            continue;
+        }
+
+        match visitor.discover(&start) {
+            Control::Break => return Ok(Control::Break),
+            Control::Prune => {
+                finished.insert(start.clone());
+                if visitor.finish(&start) == Control::Break {
+                    return Ok(Control::Break);
+                }
+                continue;
+            }
+            Control::Continue => {}
+        }
+
+        // Each entry holds a node and the iterator over its remaining relations.
+        let mut stack = vec![(start.clone(), graph.iter_local_neighbors(&start)?)];
+
+        while let Some((current_addr, neighbors)) = stack.last_mut() {
+            let Some(neighbor) = neighbors.next() else {
+                let current_addr = current_addr.clone();
+                stack.pop();
+                finished.insert(current_addr.clone());
+                if visitor.finish(&current_addr) == Control::Break {
+                    return Ok(Control::Break);
+                }
+                continue;
+            };
+
+            let current_addr = current_addr.clone();
+            let to = neighbor.address;
+            let control = if !discovered.contains(to) {
+                match visitor.tree_edge(&current_addr, neighbor.relation, to) {
+                    Control::Prune => continue,
+                    Control::Break => return Ok(Control::Break),
+                    Control::Continue => {}
+                }
+
+                discovered.insert(to.clone());
+                match visitor.discover(to) {
+                    Control::Continue => {
+                        stack.push((to.clone(), graph.iter_local_neighbors(to)?));
+                        Control::Continue
+                    }
+                    Control::Prune => {
+                        finished.insert(to.clone());
+                        visitor.finish(to)
+                    }
+                    Control::Break => Control::Break,
+                }
+            } else if !finished.contains(to) {
+                visitor.back_edge(&current_addr, neighbor.relation, to)
+            } else {
+                visitor.cross_edge(&current_addr, neighbor.relation, to)
+            };
+
+            if control == Control::Break {
+                return Ok(Control::Break);
+            }
+        }
+    }
+
+    Ok(Control::Continue)
+}
+
+/// Performs a breadth-first search from each of the `starts` in turn, reporting events to the `visitor`.
+///
+/// Start nodes that were already discovered from an earlier start are skipped,
+/// so each node is discovered and finished at most once. A node is finished
+/// once all its relations were followed.
+///
+/// ## Returns
+///
+/// [`Control::Break`] if the visitor stopped the traversal, [`Control::Continue`] otherwise.
+///
+/// ## Errors
+///
+/// Fails if any of the `starts` does not address a node of the graph.
+pub fn breadth_first_visit<G, I, V>(
+    graph: &G,
+    starts: I,
+    visitor: &mut V,
+) -> Result<Control, NodeAddressError>
+where
+    G: Graph,
+    I: IntoIterator<Item = NodeAddress>,
+    V: Visitor<G::Relation>,
+{
+    let mut discovered = HashSet::new();
+
+    for start in starts {
+        graph.local_node_data_ref(&start)?;
+        if !discovered.insert(start.clone()) {
+            continue;
+        }
+
+        match visitor.discover(&start) {
+            Control::Break => return Ok(Control::Break),
+            Control::Prune => {
+                if visitor.finish(&start) == Control::Break {
+                    return Ok(Control::Break);
+                }
+                continue;
+            }
+            Control::Continue => {}
+        }
+
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(current_addr) = queue.pop_front() {
+            for neighbor in graph.iter_local_neighbors(&current_addr)? {
+                let to = neighbor.address;
+                let control = if !discovered.contains(to) {
+                    match visitor.tree_edge(&current_addr, neighbor.relation, to) {
+                        Control::Prune => continue,
+                        Control::Break => return Ok(Control::Break),
+                        Control::Continue => {}
+                    }
+
+                    discovered.insert(to.clone());
+                    match visitor.discover(to) {
+                        Control::Continue => {
+                            queue.push_back(to.clone());
+                            Control::Continue
+                        }
+                        Control::Prune => visitor.finish(to),
+                        Control::Break => Control::Break,
+                    }
+                } else {
+                    visitor.cross_edge(&current_addr, neighbor.relation, to)
+                };
+
+                if control == Control::Break {
+                    return Ok(Control::Break);
+                }
+            }
+
+            if visitor.finish(&current_addr) == Control::Break {
+                return Ok(Control::Break);
+            }
+        }
+    }
+
+    Ok(Control::Continue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_property_graph::EmbeddedPropertyGraph;
+    #[cfg(feature = "examples-london")]
+    use crate::examples::london_graph::london_graph;
+
+    /// Records all events as strings, optionally stopping after a number of discovered nodes.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        discovered: Vec<NodeAddress>,
+        limit: Option<usize>,
+    }
+
+    impl Visitor<&str> for Recorder {
+        fn discover(&mut self, node: &NodeAddress) -> Control {
+            self.discovered.push(node.clone());
+            if self.limit == Some(self.discovered.len()) {
+                Control::Break
+            } else {
+                Control::Continue
+            }
+        }
+
+        fn tree_edge(
+            &mut self,
+            _from: &NodeAddress,
+            relation: &&str,
+            _to: &NodeAddress,
+        ) -> Control {
+            self.events.push(format!("tree {relation}"));
+            Control::Continue
+        }
+
+        fn back_edge(
+            &mut self,
+            _from: &NodeAddress,
+            relation: &&str,
+            _to: &NodeAddress,
+        ) -> Control {
+            self.events.push(format!("back {relation}"));
+            Control::Continue
+        }
+
+        fn cross_edge(
+            &mut self,
+            _from: &NodeAddress,
+            relation: &&str,
+            _to: &NodeAddress,
+        ) -> Control {
+            self.events.push(format!("cross {relation}"));
+            Control::Continue
+        }
+
+        fn finish(&mut self, node: &NodeAddress) -> Control {
+            self.events.push(format!("finish {node}"));
+            Control::Continue
+        }
+    }
+
+    /// Builds a graph with a cycle `a -> b -> c -> a`, a shortcut `a -> c`
+    /// and a node `d` that only links into the cycle.
+    fn sample_graph() -> (
+        EmbeddedPropertyGraph<&'static str, &'static str>,
+        [NodeAddress; 4],
+    ) {
+        let mut graph = EmbeddedPropertyGraph::default();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| graph.add(name));
+        graph.link_to(&a, &b, "ab").unwrap();
+        graph.link_to(&b, &c, "bc").unwrap();
+        graph.link_to(&c, &a, "ca").unwrap();
+        graph.link_to(&a, &c, "ac").unwrap();
+        graph.link_to(&d, &c, "dc").unwrap();
+        (graph, [a, b, c, d])
+    }
+
+    #[test]
+    fn depth_first_events() {
+        let (graph, [a, b, c, d]) = sample_graph();
+        let mut recorder = Recorder::default();
+        let control = depth_first_visit(&graph, [a.clone(), d.clone()], &mut recorder).unwrap();
+
+        assert_eq!(control, Control::Continue);
+        assert_eq!(
+            recorder.discovered,
+            [a.clone(), b.clone(), c.clone(), d.clone()]
+        );
+        assert_eq!(
+            recorder.events,
+            [
+                "tree ab".to_string(),
+                "tree bc".to_string(),
+                "back ca".to_string(),
+                format!("finish {c}"),
+                format!("finish {b}"),
+                "cross ac".to_string(),
+                format!("finish {a}"),
+                "cross dc".to_string(),
+                format!("finish {d}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn breadth_first_events() {
+        let (graph, [a, b, c, _]) = sample_graph();
+        let mut recorder = Recorder::default();
+        breadth_first_visit(&graph, [a.clone()], &mut recorder).unwrap();
+
+        assert_eq!(recorder.discovered, [a.clone(), b.clone(), c.clone()]);
+        assert_eq!(
+            recorder.events,
+            [
+                "tree ab".to_string(),
+                "tree ac".to_string(),
+                format!("finish {a}"),
+                "cross bc".to_string(),
+                format!("finish {b}"),
+                "cross ca".to_string(),
+                format!("finish {c}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn visitor_can_stop() {
+        let (graph, [a, b, _, d]) = sample_graph();
+        let mut recorder = Recorder {
+            limit: Some(2),
+            ..Recorder::default()
+        };
+        let control = depth_first_visit(&graph, [a.clone(), d], &mut recorder).unwrap();
+
+        assert_eq!(control, Control::Break);
+        assert_eq!(recorder.discovered, [a, b]);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn lazy_iterators() {
+        let graph = london_graph();
+        let start = NodeAddress::from_local(graph.id(), 0);
+
+        let bfs: Vec<_> = Bfs::new(&graph, start.clone())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(bfs.len(), 198);
+        assert_eq!(bfs[0], start);
+
+        let dfs: Vec<_> = Dfs::new(&graph, start.clone())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(dfs.len(), 198);
+        assert_eq!(dfs[0], start);
+
+        let first: Vec<_> = Dfs::new(&graph, start)
+            .unwrap()
+            .take(3)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(first, dfs[..3]);
+    }
+
+    #[test]
+    fn iterators_match_visitors() {
+        let (graph, [a, ..]) = sample_graph();
+
+        let mut recorder = Recorder::default();
+        depth_first_visit(&graph, [a.clone()], &mut recorder).unwrap();
+        let dfs: Vec<_> = Dfs::new(&graph, a.clone())
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(dfs, recorder.discovered);
+
+        let mut recorder = Recorder::default();
+        breadth_first_visit(&graph, [a.clone()], &mut recorder).unwrap();
+        let bfs: Vec<_> = Bfs::new(&graph, a).unwrap().map(Result::unwrap).collect();
+        assert_eq!(bfs, recorder.discovered);
+    }
+
+    #[test]
+    #[cfg(feature = "examples-london")]
+    fn unknown_node() {
+        let graph = london_graph();
+        let address = NodeAddress::from_local(graph.id(), 1000);
+        assert!(matches!(
+            Bfs::new(&graph, address.clone()),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+
+        let (graph, ..) = sample_graph();
+        let address = NodeAddress::from_local(graph.id(), 1000);
+        assert!(matches!(
+            depth_first_visit(&graph, [address], &mut Recorder::default()),
+            Err(NodeAddressError::NodeNotFound(_))
+        ));
+    }
+}